@@ -61,11 +61,11 @@ where
         .collect();
     let mut dylib_files_iter = Vec::into_iter(dylib_files);
     match (dylib_files_iter.next(), dylib_files_iter.next()) {
-        (None, None) => eyre::bail!(
+        (None, None) => Err(eyre::eyre!(
             "Compilation resulted in no '.{}' target files. \
                  Please check that your project contains a NEAR smart contract.",
             <T as ArtifactType>::extension(),
-        ),
+        )),
         (Some(path), None) => Ok(CompilationArtifact {
             path,
             fresh: !compile_artifact.fresh,
@@ -73,11 +73,11 @@ where
             builder_version_info: None,
             artifact_type: PhantomData,
         }),
-        _ => eyre::bail!(
+        _ => Err(eyre::eyre!(
             "Compilation resulted in more than one '.{}' target file: {:?}",
             <T as ArtifactType>::extension(),
             dylib_files_iter.as_slice()
-        ),
+        )),
     }
 }
 /// Runs a non-artifact-producing cargo subcommand (`check` or `clippy`) with the manifest
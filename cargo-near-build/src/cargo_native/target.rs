@@ -65,10 +65,10 @@ fn get_rustc_wasm32_unknown_unknown_target_libdir(
     if output.status.success() {
         Ok(String::from_utf8(output.stdout)?.trim().into())
     } else {
-        eyre::bail!(
+        Err(eyre::eyre!(
             "Getting rustc's wasm32-unknown-unknown target wasn't successful. Got {}",
             output.status,
-        )
+        ))
     }
 }
 
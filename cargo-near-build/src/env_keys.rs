@@ -43,6 +43,16 @@ pub(crate) const CARGO_NEAR_ABI_SCHEMA_VERSION: &str = "CARGO_NEAR_ABI_SCHEMA_VE
 
 pub const COLOR_PREFERENCE_NO_COLOR: &str = "NO_COLOR";
 
+/// container runtime of reproducible builds: `docker` (default), `podman` or `nerdctl`;
+/// `--container-runtime` flag takes precedence over it
+#[cfg(feature = "docker")]
+pub const CARGO_NEAR_CONTAINER_RUNTIME: &str = "CARGO_NEAR_CONTAINER_RUNTIME";
+
+/// number of attempts to reach the remote, when checking that built commit has been pushed to it;
+/// 5 by default
+#[cfg(feature = "docker")]
+pub const CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS: &str = "CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS";
+
 use std::collections::HashMap;
 
 pub mod nep330 {
@@ -50,6 +50,52 @@ pub(crate) mod types;
 pub mod abi {
     pub use crate::near::abi::build;
     pub use crate::types::near::abi::Opts as AbiOpts;
+
+    /// `cargo near abi lint` entry point: check generated ABI against rules,
+    /// configured in `[package.metadata.near.abi.lints]`
+    pub mod lint {
+        pub use crate::near::abi::lint::{check, run as lint};
+        pub use crate::types::near::abi::lint::{Config, Finding, Level, Rule};
+    }
+
+    /// `cargo near abi check-compat` entry point: report changes of ABI, which break
+    /// callers of the contract built from another git revision
+    #[cfg(feature = "docker")]
+    pub mod compat {
+        pub use crate::near::abi::compat::{diff, run as check_compat};
+        pub use crate::types::near::abi::compat::Incompatibility;
+    }
+
+    /// `cargo near abi migrate` entry point: convert ABI files of older schema versions
+    /// to the current one
+    pub mod migrate {
+        pub use crate::near::abi::migrate::{migrate, run as migrate_file};
+        pub use crate::types::near::abi::migrate::LossyConversion;
+    }
+
+    /// `cargo near abi mock` entry point: generate a contract crate with the same
+    /// interface as the one, described by an ABI file
+    pub mod mock {
+        pub use crate::near::abi::mock::run as mock;
+    }
+
+    /// patterns, selecting functions kept in generated ABI, configured with
+    /// `[package.metadata.near.abi]` `include`/`exclude` and `--abi-include`/`--abi-exclude`
+    pub mod filter {
+        pub use crate::types::near::abi::filter::{Filter, Pattern};
+    }
+
+    /// `cargo near abi --check` entry point: compare generated ABI with a committed ABI file
+    pub mod snapshot {
+        pub use crate::near::abi::snapshot::check;
+    }
+
+    /// `cargo near abi validate-args` entry point: validate JSON arguments of a function call
+    /// against contract's ABI
+    pub mod validate {
+        pub use crate::near::abi::validate::{check, read_abi, run as validate_args, validate};
+        pub use crate::types::near::abi::validate::{Severity, ValidationError};
+    }
 }
 
 /// `cargo near check` entry point: type-check a contract under the same environment
@@ -103,9 +149,44 @@ pub mod extended {
 
 #[cfg(feature = "docker")]
 pub mod docker {
+    pub use crate::near::docker_build::list_variants;
     pub use crate::near::docker_build::run as build;
+    pub use crate::near::docker_build::run_all_variants as build_all_variants;
     pub use crate::types::near::build::input::BuildContext;
     pub use crate::types::near::docker_build::Opts as DockerBuildOpts;
+    pub use crate::types::near::docker_build::RemoteSource;
+    pub use crate::types::near::docker_build::container_runtime::ContainerRuntime;
+
+    /// upgrade of docker image of `[package.metadata.near.reproducible_build]` in contract's `Cargo.toml`
+    pub mod upgrade_image {
+        pub use crate::near::docker_build::upgrade_image::{
+            Change, SOURCE_SCAN_IMAGE, run as upgrade_image, select_latest, upgrade_manifest,
+        };
+        pub use crate::types::near::docker_build::upgrade_image::{
+            ImageTag, Opts as UpgradeImageOpts,
+        };
+    }
+
+    /// verification of a wasm against its NEP-330 contract source metadata
+    pub mod verify {
+        pub use crate::near::docker_build::verify::run as verify;
+        pub use crate::types::near::docker_build::verify::{ExpectedHash, Opts as VerifyOpts};
+    }
+}
+
+/// execution of contract's view methods without a NEAR node
+#[cfg(feature = "build_internal")]
+pub mod run_view {
+    pub use crate::near::run_view::run as run_view;
+    pub use crate::types::near::run_view::{Opts as RunViewOpts, ViewOutcome};
+}
+
+/// inspection of contract's wasm without a NEAR node
+#[cfg(all(feature = "build_internal", feature = "docker"))]
+pub mod inspect {
+    pub use crate::near::inspect::{
+        CONTRACT_SOURCE_METADATA, contract_source_metadata, run as inspect_metadata,
+    };
 }
 
 #[cfg(feature = "test_code")]
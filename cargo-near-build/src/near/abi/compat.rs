@@ -0,0 +1,479 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use colored::Colorize;
+use near_abi::{AbiFunction, AbiFunctionKind, AbiFunctionModifier, AbiParameters, AbiType};
+use schemars::schema::RootSchema;
+
+use crate::pretty_print;
+use crate::types::cargo::metadata::CrateMetadata;
+use crate::types::near::abi as abi_types;
+use crate::types::near::abi::compat::Incompatibility;
+use crate::types::near::build::input::ColorPreference;
+use crate::types::near::docker_build::{cloned_repo::ClonedRepo, crate_in_repo};
+
+use super::schema::definition_name;
+
+/// Generates ABI of the contract in current tree and at git `revision` with the same features,
+/// and reports changes, which break callers of the contract deployed from `revision`.
+///
+/// Returns an error if any incompatible change has been found.
+pub fn run(args: abi_types::Opts, revision: &str) -> eyre::Result<Vec<Incompatibility>> {
+    let no_locked = args.no_locked;
+    let features = args.features.clone();
+    let filter = abi_types::filter::Filter::new(&args.abi_include, &args.abi_exclude)?;
+    let color = args.color.unwrap_or(ColorPreference::Auto);
+
+    let (crate_metadata, current_abi) = super::collect_and_generate(args)?;
+    let previous_abi = abi_at_revision(
+        &crate_metadata,
+        no_locked,
+        features.as_deref(),
+        &filter,
+        color,
+        revision,
+    )?;
+
+    let incompatibilities = diff(&previous_abi, &current_abi);
+    for incompatibility in incompatibilities.iter() {
+        eprintln!("{incompatibility}");
+    }
+    if !incompatibilities.is_empty() {
+        eprintln!();
+        return Err(eyre::eyre!(
+            "ABI is incompatible with `{}`: {} breaking change(s)",
+            revision,
+            incompatibilities.len()
+        ));
+    }
+    pretty_print::success(&format!("ABI is compatible with `{revision}`"));
+    Ok(incompatibilities)
+}
+
+/// generates ABI of the contract, described by `crate_metadata`, at git `revision`
+/// of its repo, checked out into a temporary clone
+pub(crate) fn abi_at_revision(
+    crate_metadata: &CrateMetadata,
+    no_locked: bool,
+    features: Option<&str>,
+    filter: &abi_types::filter::Filter,
+    color: ColorPreference,
+    revision: &str,
+) -> eyre::Result<near_abi::AbiRoot> {
+    let crate_in_repo = pretty_print::handle_step(
+        "Opening repo and determining relative path of contract...",
+        || crate_in_repo::Crate::find(&crate_metadata.manifest_path.directory()?.to_path_buf()),
+    )?;
+    let cloned_repo = pretty_print::handle_step(
+        &format!(
+            "Cloning project repo at `{}` to a temporary site...",
+            revision.magenta()
+        ),
+        || ClonedRepo::clone_at_revision(crate_in_repo, no_locked, revision),
+    )?;
+    super::generate::procedure(
+        cloned_repo.crate_metadata(),
+        no_locked,
+        false,
+        true,
+        &super::cargo_feature_args(features),
+        filter,
+        &[],
+        color,
+    )
+}
+
+/// Changes from `previous` to `current` ABI, which break existing callers of `previous`
+///
+/// Additions of functions and optional arguments, removal of arguments (unknown json fields
+/// are ignored by `near-sdk`) and documentation changes are compatible.
+pub fn diff(previous: &near_abi::AbiRoot, current: &near_abi::AbiRoot) -> Vec<Incompatibility> {
+    let current_functions = current
+        .body
+        .functions
+        .iter()
+        .map(|function| (function.name.as_str(), function))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut result = vec![];
+    for previous_function in previous.body.functions.iter() {
+        let mut report = |message: String| {
+            result.push(Incompatibility {
+                function: previous_function.name.clone(),
+                message,
+            })
+        };
+        let Some(current_function) = current_functions.get(previous_function.name.as_str()) else {
+            report("was removed".to_string());
+            continue;
+        };
+        let previous_function = Function {
+            abi: previous_function,
+            root_schema: &previous.body.root_schema,
+        };
+        let current_function = Function {
+            abi: current_function,
+            root_schema: &current.body.root_schema,
+        };
+        for message in function_diff(&previous_function, &current_function) {
+            report(message);
+        }
+    }
+    result
+}
+
+struct Function<'a> {
+    abi: &'a AbiFunction,
+    root_schema: &'a RootSchema,
+}
+
+impl Function<'_> {
+    fn has(&self, modifier: AbiFunctionModifier) -> bool {
+        self.abi.modifiers.contains(&modifier)
+    }
+
+    fn resolved(&self, type_schema: &schemars::schema::Schema) -> serde_json::Value {
+        resolve(
+            serde_json::to_value(type_schema).unwrap_or_default(),
+            self.root_schema,
+            &mut BTreeSet::new(),
+        )
+    }
+}
+
+fn same_type(
+    previous: &Function,
+    previous_type: &AbiType,
+    current: &Function,
+    current_type: &AbiType,
+) -> bool {
+    match (previous_type, current_type) {
+        (
+            AbiType::Json {
+                type_schema: previous_schema,
+            },
+            AbiType::Json {
+                type_schema: current_schema,
+            },
+        ) => previous.resolved(previous_schema) == current.resolved(current_schema),
+        (
+            AbiType::Borsh {
+                type_schema: previous_schema,
+            },
+            AbiType::Borsh {
+                type_schema: current_schema,
+            },
+        ) => previous_schema == current_schema,
+        _ => false,
+    }
+}
+
+fn function_diff(previous: &Function, current: &Function) -> Vec<String> {
+    let mut result = vec![];
+    if previous.abi.kind == AbiFunctionKind::View && current.abi.kind == AbiFunctionKind::Call {
+        result.push("changed from view to call, it can no longer be called with view RPC".into());
+    }
+    if !previous.has(AbiFunctionModifier::Private) && current.has(AbiFunctionModifier::Private) {
+        result.push("became `#[private]`".into());
+    }
+    if previous.has(AbiFunctionModifier::Payable) && !current.has(AbiFunctionModifier::Payable) {
+        result.push("is no longer `#[payable]`, calls attaching a deposit will fail".into());
+    }
+    if previous.has(AbiFunctionModifier::Init) != current.has(AbiFunctionModifier::Init) {
+        result.push("changed whether it is an `#[init]` function".into());
+    }
+
+    match (&previous.abi.params, &current.abi.params) {
+        (
+            AbiParameters::Json {
+                args: previous_args,
+            },
+            AbiParameters::Json { args: current_args },
+        ) => {
+            let previous_args = previous_args
+                .iter()
+                .map(|arg| (arg.name.as_str(), arg))
+                .collect::<BTreeMap<_, _>>();
+            for current_arg in current_args {
+                let current_schema = current.resolved(&current_arg.type_schema);
+                match previous_args.get(current_arg.name.as_str()) {
+                    None if !accepts_null(&current_schema) => result.push(format!(
+                        "has a new required argument `{}`",
+                        current_arg.name
+                    )),
+                    Some(previous_arg)
+                        if previous.resolved(&previous_arg.type_schema) != current_schema =>
+                    {
+                        result.push(format!("changed type of argument `{}`", current_arg.name));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (
+            AbiParameters::Borsh {
+                args: previous_args,
+            },
+            AbiParameters::Borsh { args: current_args },
+        ) => {
+            if previous_args != current_args {
+                result.push("changed borsh arguments".into());
+            }
+        }
+        (AbiParameters::Json { .. }, AbiParameters::Borsh { .. }) => {
+            result.push("changed serialization of arguments from json to borsh".into());
+        }
+        (AbiParameters::Borsh { .. }, AbiParameters::Json { .. }) => {
+            result.push("changed serialization of arguments from borsh to json".into());
+        }
+    }
+
+    match (&previous.abi.result, &current.abi.result) {
+        (Some(_), None) => result.push("no longer returns a value".into()),
+        (Some(previous_result), Some(current_result))
+            if !same_type(previous, previous_result, current, current_result) =>
+        {
+            result.push("changed type of returned value".into());
+        }
+        _ => {}
+    }
+    result
+}
+
+/// inlines `$ref`-s to definitions of `root_schema` and drops descriptions and titles of schemas,
+/// so that renames of definitions and doc changes don't affect comparison
+///
+/// recursive references are kept as is
+fn resolve(
+    value: serde_json::Value,
+    root_schema: &RootSchema,
+    resolving: &mut BTreeSet<String>,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(mut object) => {
+            object.remove("description");
+            object.remove("title");
+            let reference = object
+                .get("$ref")
+                .and_then(|reference| reference.as_str())
+                .and_then(definition_name)
+                .map(str::to_string);
+            if let Some(name) = reference {
+                if let Some(definition) = root_schema.definitions.get(&name) {
+                    if resolving.insert(name.clone()) {
+                        let definition = serde_json::to_value(definition).unwrap_or_default();
+                        let resolved = resolve(definition, root_schema, resolving);
+                        resolving.remove(&name);
+                        return resolved;
+                    }
+                }
+            }
+            serde_json::Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let value = match key.as_str() {
+                            // maps of names to schemas, names are kept as is, even `description` or `title` ones
+                            "properties" | "patternProperties" | "definitions" => {
+                                resolve_map(value, root_schema, resolving)
+                            }
+                            // values of instances, not schemas
+                            "enum" | "const" | "default" | "examples" | "required" => value,
+                            _ => resolve(value, root_schema, resolving),
+                        };
+                        (key, value)
+                    })
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(array) => serde_json::Value::Array(
+            array
+                .into_iter()
+                .map(|value| resolve(value, root_schema, resolving))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn resolve_map(
+    value: serde_json::Value,
+    root_schema: &RootSchema,
+    resolving: &mut BTreeSet<String>,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(name, schema)| (name, resolve(schema, root_schema, resolving)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// whether a resolved json schema accepts `null`, i.e. the argument may be omitted
+fn accepts_null(schema: &serde_json::Value) -> bool {
+    match schema.get("type") {
+        Some(serde_json::Value::String(ty)) if ty == "null" => return true,
+        Some(serde_json::Value::Array(types)) if types.iter().any(|ty| ty == "null") => {
+            return true;
+        }
+        _ => {}
+    }
+    ["anyOf", "oneOf"].into_iter().any(|key| {
+        schema
+            .get(key)
+            .and_then(|variants| variants.as_array())
+            .is_some_and(|variants| variants.iter().any(accepts_null))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root(functions: serde_json::Value, definitions: serde_json::Value) -> near_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": {},
+            "body": {
+                "functions": functions,
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": definitions,
+                }
+            },
+        }))
+        .unwrap()
+    }
+
+    fn messages(incompatibilities: Vec<Incompatibility>) -> Vec<String> {
+        incompatibilities
+            .into_iter()
+            .map(|incompatibility| {
+                format!("{} {}", incompatibility.function, incompatibility.message)
+            })
+            .collect()
+    }
+
+    fn transfer(args: serde_json::Value) -> serde_json::Value {
+        serde_json::json!([{
+            "name": "transfer",
+            "kind": "call",
+            "modifiers": ["payable"],
+            "params": { "serialization_type": "json", "args": args },
+            "result": { "serialization_type": "json", "type_schema": { "$ref": "#/definitions/Receipt" } }
+        }])
+    }
+
+    #[test]
+    fn test_compatible_changes() {
+        let previous = abi_root(
+            transfer(serde_json::json!([
+                { "name": "amount", "type_schema": { "type": "string" } },
+                { "name": "memo", "type_schema": { "type": ["string", "null"] } }
+            ])),
+            serde_json::json!({ "Receipt": { "type": "object", "properties": { "id": { "type": "string" } } } }),
+        );
+        let current = abi_root(
+            serde_json::json!([
+                {
+                    "name": "transfer",
+                    "doc": " new docs",
+                    "kind": "call",
+                    "modifiers": ["payable"],
+                    "params": {
+                        "serialization_type": "json",
+                        "args": [
+                            { "name": "amount", "type_schema": { "type": "string" } },
+                            { "name": "note", "type_schema": { "anyOf": [{ "$ref": "#/definitions/Note" }, { "type": "null" }] } }
+                        ]
+                    },
+                    "result": { "serialization_type": "json", "type_schema": { "$ref": "#/definitions/TransferReceipt" } }
+                },
+                { "name": "added", "kind": "view" }
+            ]),
+            serde_json::json!({
+                "TransferReceipt": { "type": "object", "description": "renamed", "properties": { "id": { "type": "string" } } },
+                "Note": { "type": "string" }
+            }),
+        );
+        assert_eq!(diff(&previous, &current), vec![]);
+    }
+
+    #[test]
+    fn test_incompatible_changes() {
+        let previous = abi_root(
+            serde_json::json!([
+                { "name": "get", "kind": "view" },
+                { "name": "removed", "kind": "view" },
+                {
+                    "name": "transfer",
+                    "kind": "call",
+                    "modifiers": ["payable"],
+                    "params": { "serialization_type": "json", "args": [
+                        { "name": "amount", "type_schema": { "$ref": "#/definitions/U128" } }
+                    ] },
+                    "result": { "serialization_type": "json", "type_schema": { "type": "string" } }
+                }
+            ]),
+            serde_json::json!({ "U128": { "type": "string" } }),
+        );
+        let current = abi_root(
+            serde_json::json!([
+                { "name": "get", "kind": "call", "modifiers": ["private"] },
+                {
+                    "name": "transfer",
+                    "kind": "call",
+                    "params": { "serialization_type": "json", "args": [
+                        { "name": "amount", "type_schema": { "type": "integer", "format": "uint128" } },
+                        { "name": "receiver_id", "type_schema": { "type": "string" } }
+                    ] },
+                    "result": { "serialization_type": "json", "type_schema": { "type": "integer" } }
+                }
+            ]),
+            serde_json::json!({}),
+        );
+        assert_eq!(
+            messages(diff(&previous, &current)),
+            vec![
+                "get changed from view to call, it can no longer be called with view RPC",
+                "get became `#[private]`",
+                "removed was removed",
+                "transfer is no longer `#[payable]`, calls attaching a deposit will fail",
+                "transfer changed type of argument `amount`",
+                "transfer has a new required argument `receiver_id`",
+                "transfer changed type of returned value",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_properties_named_like_schema_keywords() {
+        let book = |title_type: &str| {
+            abi_root(
+                serde_json::json!([{
+                    "name": "add_book",
+                    "kind": "call",
+                    "params": { "serialization_type": "json", "args": [
+                        { "name": "book", "type_schema": { "$ref": "#/definitions/Book" } }
+                    ] }
+                }]),
+                serde_json::json!({ "Book": {
+                    "type": "object",
+                    "required": ["title", "description"],
+                    "properties": {
+                        "title": { "type": title_type },
+                        "description": { "type": "string", "description": "changes of docs are fine" }
+                    }
+                } }),
+            )
+        };
+        assert_eq!(diff(&book("string"), &book("string")), vec![]);
+        assert_eq!(
+            messages(diff(&book("string"), &book("integer"))),
+            vec!["add_book changed type of argument `book`"]
+        );
+    }
+}
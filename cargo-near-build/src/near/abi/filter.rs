@@ -0,0 +1,126 @@
+use crate::types::near::abi::filter::Filter;
+
+use super::schema::{function_json_schemas, reachable_definitions};
+
+/// Removes functions of `abi_root`, not kept by `filter`, along with schema definitions,
+/// which were reachable only from removed functions.
+///
+/// Definitions, which weren't reachable from any function in the first place,
+/// are left in place, so that `unused_definitions` lint still reports them.
+///
+/// Returns names of removed functions.
+pub(crate) fn apply(
+    abi_root: &mut near_abi::AbiRoot,
+    filter: &Filter,
+) -> eyre::Result<Vec<String>> {
+    if filter.is_empty() {
+        return Ok(vec![]);
+    }
+    let reachable = |abi_root: &near_abi::AbiRoot| {
+        let roots = abi_root
+            .body
+            .functions
+            .iter()
+            .flat_map(function_json_schemas);
+        reachable_definitions(&abi_root.body.root_schema, roots)
+    };
+
+    let reachable_before = reachable(abi_root);
+    let (kept, removed) = std::mem::take(&mut abi_root.body.functions)
+        .into_iter()
+        .partition::<Vec<_>, _>(|function| filter.keeps(function));
+    abi_root.body.functions = kept;
+    let reachable_after = reachable(abi_root);
+
+    abi_root
+        .body
+        .root_schema
+        .definitions
+        .retain(|name, _| !reachable_before.contains(name) || reachable_after.contains(name));
+    Ok(removed.into_iter().map(|function| function.name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root() -> near_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": {},
+            "body": {
+                "functions": [
+                    {
+                        "name": "get_owner",
+                        "kind": "view",
+                        "result": { "serialization_type": "json", "type_schema": { "$ref": "#/definitions/Owner" } }
+                    },
+                    {
+                        "name": "on_transfer_resolved",
+                        "kind": "call",
+                        "modifiers": ["private"],
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [{ "name": "report", "type_schema": { "$ref": "#/definitions/Report" } }]
+                        }
+                    },
+                    {
+                        "name": "internal_sync",
+                        "kind": "call"
+                    }
+                ],
+                "root_schema": {
+                    "definitions": {
+                        "Owner": { "type": "string" },
+                        "Report": { "type": "object", "properties": { "entry": { "$ref": "#/definitions/ReportEntry" } } },
+                        "ReportEntry": { "type": "string" },
+                        "Orphan": { "type": "string" }
+                    }
+                }
+            },
+        }))
+        .unwrap()
+    }
+
+    fn function_names(abi_root: &near_abi::AbiRoot) -> Vec<&str> {
+        abi_root
+            .body
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_exclude_prunes_unreachable_definitions() {
+        let mut abi_root = abi_root();
+        let filter = Filter::new(&[], &["@private".into(), "internal_*".into()]).unwrap();
+
+        let removed = apply(&mut abi_root, &filter).unwrap();
+
+        assert_eq!(removed, vec!["on_transfer_resolved", "internal_sync"]);
+        assert_eq!(function_names(&abi_root), vec!["get_owner"]);
+        let definitions = abi_root
+            .body
+            .root_schema
+            .definitions
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(definitions, vec!["Orphan", "Owner"]);
+    }
+
+    #[test]
+    fn test_include_then_exclude() {
+        let mut abi_root = abi_root();
+        let filter = Filter::new(&["*_*".into()], &["get_?wner".into()]).unwrap();
+
+        apply(&mut abi_root, &filter).unwrap();
+
+        assert_eq!(
+            function_names(&abi_root),
+            vec!["on_transfer_resolved", "internal_sync"]
+        );
+        assert!(Filter::new(&["@internal".into()], &[]).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::types::near::abi::filter::Filter;
 use crate::types::near::build::input::ColorPreference;
 use crate::{
     cargo_native::{self, Dylib},
@@ -10,12 +11,14 @@ use eyre::ContextCompat;
 
 pub mod dylib;
 
+#[allow(clippy::too_many_arguments)]
 pub fn procedure(
     crate_metadata: &CrateMetadata,
     no_locked: bool,
     generate_docs: bool,
     hide_warnings: bool,
     cargo_feature_args: &[&str],
+    filter: &Filter,
     env: &[(&str, &str)],
     color: ColorPreference,
 ) -> eyre::Result<near_abi::AbiRoot> {
@@ -118,6 +121,13 @@ pub fn procedure(
         strip_docs(&mut contract_abi);
     }
 
+    // patterns of `[package.metadata.near.abi]` go first, cli ones extend them
+    let filter = Filter::parse(crate_metadata)?.merge(filter);
+    let excluded = super::filter::apply(&mut contract_abi, &filter)?;
+    if !excluded.is_empty() {
+        tracing::info!("excluded functions from ABI: {}", excluded.join(", "));
+    }
+
     Ok(contract_abi)
 }
 
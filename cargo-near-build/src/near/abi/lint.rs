@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use colored::Colorize;
+use near_abi::{AbiFunctionKind, AbiFunctionModifier};
+use schemars::schema::{InstanceType, Schema, SingleOrVec};
+
+use crate::pretty_print;
+use crate::types::near::abi as abi_types;
+use crate::types::near::abi::lint::{Config, Finding, Level, Rule};
+
+use super::schema;
+
+/// Generates ABI of the contract and checks it against rules,
+/// configured in `[package.metadata.near.abi.lints]` section of contract's `Cargo.toml`.
+///
+/// Returns an error if any finding of a rule with [`Level::Deny`] has been reported.
+pub fn run(args: abi_types::Opts) -> eyre::Result<Vec<Finding>> {
+    let docs_generated = !args.no_doc;
+    let (crate_metadata, contract_abi) = super::collect_and_generate(args)?;
+
+    let config = pretty_print::handle_step(
+        &format!(
+            "Parsing `{}` section of contract's `Cargo.toml` ...",
+            crate::types::near::abi::lint::SECTION_NAME.magenta()
+        ),
+        || Config::parse(&crate_metadata),
+    )?;
+
+    let mut findings = check(&contract_abi, &config);
+    if !docs_generated {
+        findings.retain(|finding| finding.rule != Rule::MissingDocs);
+    }
+
+    for finding in findings.iter() {
+        eprintln!("{finding}");
+    }
+    let denied = findings
+        .iter()
+        .filter(|finding| finding.level == Level::Deny)
+        .count();
+    let warned = findings.len() - denied;
+    if !findings.is_empty() {
+        eprintln!();
+    }
+    if denied > 0 {
+        return Err(eyre::eyre!(
+            "ABI lint failed: {} error(s), {} warning(s)",
+            denied,
+            warned
+        ));
+    }
+    pretty_print::success(&format!("ABI lint passed with {warned} warning(s)"));
+    Ok(findings)
+}
+
+/// Checks `abi_root` against all rules, not allowed by `config`
+pub fn check(abi_root: &near_abi::AbiRoot, config: &Config) -> Vec<Finding> {
+    let mut findings = vec![];
+    for rule in Rule::ALL {
+        let level = config.level(rule);
+        if level == Level::Allow {
+            continue;
+        }
+        let messages = match rule {
+            Rule::MissingDocs => missing_docs(abi_root),
+            Rule::JsonLargeIntegers => json_large_integers(abi_root),
+            Rule::PayableView => payable_view(abi_root),
+            Rule::UnguardedInit => unguarded_init(abi_root),
+            Rule::UnusedDefinitions => unused_definitions(abi_root),
+            Rule::Naming => naming(abi_root),
+        };
+        findings.extend(messages.into_iter().map(|message| Finding {
+            rule,
+            level,
+            message,
+        }));
+    }
+    findings
+}
+
+fn is_private(function: &near_abi::AbiFunction) -> bool {
+    function.modifiers.contains(&AbiFunctionModifier::Private)
+}
+
+fn missing_docs(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    abi_root
+        .body
+        .functions
+        .iter()
+        .filter(|function| !is_private(function))
+        .filter(|function| {
+            function
+                .doc
+                .as_deref()
+                .is_none_or(|doc| doc.trim().is_empty())
+        })
+        .map(|function| format!("public function `{}` has no documentation", function.name))
+        .collect()
+}
+
+const LARGE_INTEGER_FORMATS: [&str; 4] = ["uint64", "int64", "uint128", "int128"];
+
+/// path to the first `u64`/`i64`/`u128`/`i128` JSON number, found in `schema`,
+/// following references into `root_schema.definitions`
+fn find_large_integer(
+    root_schema: &schemars::schema::RootSchema,
+    schema: &Schema,
+    path: String,
+    visited: &mut BTreeSet<String>,
+) -> Option<(String, String)> {
+    let Schema::Object(object) = schema else {
+        return None;
+    };
+    let is_integer = match &object.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => **instance_type == InstanceType::Integer,
+        Some(SingleOrVec::Vec(instance_types)) => instance_types.contains(&InstanceType::Integer),
+        None => false,
+    };
+    if let (true, Some(format)) = (is_integer, object.format.as_deref()) {
+        if LARGE_INTEGER_FORMATS.contains(&format) {
+            return Some((path, format.to_string()));
+        }
+    }
+    if let Some(name) = object
+        .reference
+        .as_deref()
+        .and_then(schema::definition_name)
+    {
+        if visited.insert(name.to_string()) {
+            if let Some(definition) = root_schema.definitions.get(name) {
+                let found = find_large_integer(root_schema, definition, path.clone(), visited);
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+    }
+    if let Some(object_validation) = &object.object {
+        for (property, property_schema) in object_validation.properties.iter() {
+            let found = find_large_integer(
+                root_schema,
+                property_schema,
+                format!("{path}.{property}"),
+                visited,
+            );
+            if found.is_some() {
+                return found;
+            }
+        }
+    }
+    schema::children(object)
+        .into_iter()
+        .find_map(|child| find_large_integer(root_schema, child, path.clone(), visited))
+}
+
+fn json_large_integers(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    let mut result = vec![];
+    for function in abi_root.body.functions.iter() {
+        if function.kind != AbiFunctionKind::Call {
+            continue;
+        }
+        let near_abi::AbiParameters::Json { args } = &function.params else {
+            continue;
+        };
+        for arg in args {
+            let mut visited = BTreeSet::new();
+            if let Some((path, format)) = find_large_integer(
+                &abi_root.body.root_schema,
+                &arg.type_schema,
+                arg.name.clone(),
+                &mut visited,
+            ) {
+                result.push(format!(
+                    "argument `{}` of call function `{}` is a JSON number of `{}` format at `{}`, \
+                    which loses precision in JavaScript; use `near_sdk::json_types::U64`/`U128` string wrappers",
+                    arg.name, function.name, format, path
+                ));
+            }
+        }
+    }
+    result
+}
+
+fn payable_view(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    abi_root
+        .body
+        .functions
+        .iter()
+        .filter(|function| {
+            function.kind == AbiFunctionKind::View
+                && function.modifiers.contains(&AbiFunctionModifier::Payable)
+        })
+        .map(|function| {
+            format!(
+                "view function `{}` is marked payable, but view calls cannot attach a deposit",
+                function.name
+            )
+        })
+        .collect()
+}
+
+fn unguarded_init(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    abi_root
+        .body
+        .functions
+        .iter()
+        .filter(|function| {
+            function.modifiers.contains(&AbiFunctionModifier::Init) && !is_private(function)
+        })
+        .map(|function| {
+            format!(
+                "init function `{}` is not `#[private]` and can be called by any account \
+                before the contract is initialized",
+                function.name
+            )
+        })
+        .collect()
+}
+
+fn unused_definitions(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    let reachable = schema::reachable_definitions(
+        &abi_root.body.root_schema,
+        abi_root
+            .body
+            .functions
+            .iter()
+            .flat_map(schema::function_json_schemas),
+    );
+    abi_root
+        .body
+        .root_schema
+        .definitions
+        .keys()
+        .filter(|name| !reachable.contains(*name))
+        .map(|name| format!("schema definition `{name}` is not referenced by any function"))
+        .collect()
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_camel_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().any(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn naming(abi_root: &near_abi::AbiRoot) -> Vec<String> {
+    let mut result = vec![];
+    for function in abi_root.body.functions.iter() {
+        if !is_snake_case(&function.name) {
+            result.push(format!(
+                "function name `{}` is not in snake_case",
+                function.name
+            ));
+        }
+        if let near_abi::AbiParameters::Json { args } = &function.params {
+            for arg in args.iter().filter(|arg| !is_snake_case(&arg.name)) {
+                result.push(format!(
+                    "argument `{}` of function `{}` is not in snake_case",
+                    arg.name, function.name
+                ));
+            }
+        }
+    }
+
+    // property names of object definitions are expected to share a single convention
+    // across the whole ABI; the less common one is reported
+    let mut snake_case_definitions = BTreeMap::new();
+    let mut camel_case_definitions = BTreeMap::new();
+    for (name, definition) in abi_root.body.root_schema.definitions.iter() {
+        let Schema::Object(object) = definition else {
+            continue;
+        };
+        let Some(object_validation) = &object.object else {
+            continue;
+        };
+        let properties = object_validation.properties.keys();
+        let camel = properties
+            .clone()
+            .filter(|property| is_camel_case(property))
+            .cloned()
+            .collect::<Vec<_>>();
+        let snake = properties
+            .filter(|property| property.contains('_') && is_snake_case(property))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !camel.is_empty() {
+            camel_case_definitions.insert(name.clone(), camel);
+        }
+        if !snake.is_empty() {
+            snake_case_definitions.insert(name.clone(), snake);
+        }
+    }
+    if !snake_case_definitions.is_empty() && !camel_case_definitions.is_empty() {
+        let (minority, convention) = if camel_case_definitions.len() <= snake_case_definitions.len()
+        {
+            (camel_case_definitions, "camelCase")
+        } else {
+            (snake_case_definitions, "snake_case")
+        };
+        for (name, properties) in minority {
+            result.push(format!(
+                "definition `{}` uses {} property names ({}), inconsistent with the rest of the ABI",
+                name,
+                convention,
+                properties.join(", ")
+            ));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root(body: serde_json::Value) -> near_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": {},
+            "body": body,
+        }))
+        .unwrap()
+    }
+
+    fn rules_of(findings: &[Finding]) -> Vec<Rule> {
+        findings.iter().map(|finding| finding.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_abi_has_no_findings() {
+        let abi = abi_root(serde_json::json!({
+            "functions": [{
+                "name": "get_balance",
+                "doc": " Returns balance",
+                "kind": "view",
+                "params": {
+                    "serialization_type": "json",
+                    "args": [{ "name": "account_id", "type_schema": { "$ref": "#/definitions/AccountId" } }]
+                },
+                "result": { "serialization_type": "json", "type_schema": { "$ref": "#/definitions/U128" } }
+            }],
+            "root_schema": {
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "String",
+                "type": "string",
+                "definitions": {
+                    "AccountId": { "type": "string" },
+                    "U128": { "type": "string" }
+                }
+            }
+        }));
+        assert_eq!(check(&abi, &Config::default()), vec![]);
+    }
+
+    #[test]
+    fn test_each_rule_is_reported() {
+        let abi = abi_root(serde_json::json!({
+            "functions": [
+                {
+                    "name": "new",
+                    "kind": "call",
+                    "modifiers": ["init"],
+                },
+                {
+                    "name": "getBalance",
+                    "doc": " view",
+                    "kind": "view",
+                    "modifiers": ["payable"],
+                },
+                {
+                    "name": "transfer",
+                    "doc": " transfer",
+                    "kind": "call",
+                    "params": {
+                        "serialization_type": "json",
+                        "args": [{ "name": "args", "type_schema": { "$ref": "#/definitions/TransferArgs" } }]
+                    }
+                }
+            ],
+            "root_schema": {
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "String",
+                "type": "string",
+                "definitions": {
+                    "TransferArgs": {
+                        "type": "object",
+                        "properties": {
+                            "amount": { "type": "integer", "format": "uint128", "minimum": 0.0 }
+                        }
+                    },
+                    "Unused": { "type": "string" }
+                }
+            }
+        }));
+        let findings = check(&abi, &Config::default());
+        assert_eq!(
+            rules_of(&findings),
+            vec![
+                Rule::MissingDocs,
+                Rule::JsonLargeIntegers,
+                Rule::PayableView,
+                Rule::UnguardedInit,
+                Rule::UnusedDefinitions,
+                Rule::Naming,
+            ]
+        );
+        assert!(findings[1].message.contains("args.amount"));
+        assert_eq!(findings[2].level, Level::Deny);
+    }
+
+    #[test]
+    fn test_private_init_and_callbacks_are_not_reported() {
+        let abi = abi_root(serde_json::json!({
+            "functions": [{
+                "name": "new",
+                "kind": "call",
+                "modifiers": ["init", "private"],
+            }],
+            "root_schema": {
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "String",
+                "type": "string",
+                "definitions": {}
+            }
+        }));
+        assert_eq!(check(&abi, &Config::default()), vec![]);
+    }
+
+    #[test]
+    fn test_inconsistent_property_naming() {
+        let abi = abi_root(serde_json::json!({
+            "functions": [],
+            "root_schema": {
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "String",
+                "type": "string",
+                "definitions": {
+                    "A": { "type": "object", "properties": { "owner_id": { "type": "string" } } },
+                    "B": { "type": "object", "properties": { "token_id": { "type": "string" } } },
+                    "C": { "type": "object", "properties": { "ownerId": { "type": "string" } } }
+                }
+            }
+        }));
+        let mut config = Config::default();
+        config.levels.insert(Rule::UnusedDefinitions, Level::Allow);
+        let findings = check(&abi, &config);
+        assert_eq!(rules_of(&findings), vec![Rule::Naming]);
+        assert!(findings[0].message.contains("`C`"));
+    }
+
+    #[test]
+    fn test_config_overrides_and_unknown_keys() {
+        let config = Config::from_configured(BTreeMap::from([
+            ("missing_docs".to_string(), Level::Deny),
+            ("naming".to_string(), Level::Allow),
+        ]))
+        .unwrap();
+        assert_eq!(config.level(Rule::MissingDocs), Level::Deny);
+        assert_eq!(config.level(Rule::Naming), Level::Allow);
+        assert_eq!(config.level(Rule::UnusedDefinitions), Level::Warn);
+
+        let err =
+            Config::from_configured(BTreeMap::from([("no_such_rule".to_string(), Level::Deny)]))
+                .unwrap_err();
+        assert!(err.to_string().contains("no_such_rule"));
+    }
+}
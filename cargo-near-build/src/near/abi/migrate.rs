@@ -0,0 +1,533 @@
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+use rustc_version::Version;
+use serde_json::{Map, Value, json};
+
+use crate::pretty_print;
+use crate::types::near::abi::migrate::LossyConversion;
+
+const ROOT_KEYS: [&str; 3] = ["schema_version", "metadata", "body"];
+const BODY_KEYS: [&str; 2] = ["functions", "root_schema"];
+const FUNCTION_KEYS: [&str; 8] = [
+    "name",
+    "doc",
+    "kind",
+    "modifiers",
+    "params",
+    "callbacks",
+    "callbacks_vec",
+    "result",
+];
+
+/// first schema version, where Borsh schemas are serialized in `borsh` 1.x format
+const BORSH_V1_SCHEMA_VERSION: Version = Version::new(0, 4, 0);
+
+/// Reads ABI file at `path`, produced by an older version of `cargo-near`/`near-abi`,
+/// converts it to current [`near_abi::SCHEMA_VERSION`] with [`migrate`] and writes it to `out`,
+/// or back to `path`, if `out` isn't set.
+///
+/// Files with `.zst` extension are decompressed on read and compressed on write.
+pub fn run(
+    path: &camino::Utf8Path,
+    out: Option<&camino::Utf8Path>,
+    compact_abi: bool,
+) -> eyre::Result<Vec<LossyConversion>> {
+    let content = super::validate::read_abi_bytes(path)?;
+    let abi: Value = serde_json::from_slice(&content)
+        .wrap_err_with(|| format!("unable to parse ABI file `{path}`"))?;
+    let (contract_abi, lossy_conversions) = migrate(abi)?;
+    for lossy in lossy_conversions.iter() {
+        eprintln!("{lossy}");
+    }
+
+    let out = out.unwrap_or(path);
+    let content = if compact_abi {
+        serde_json::to_vec(&contract_abi)?
+    } else {
+        serde_json::to_vec_pretty(&contract_abi)?
+    };
+    let content = if out.extension() == Some("zst") {
+        zstd::encode_all(content.as_slice(), *zstd::compression_level_range().end())?
+    } else {
+        content
+    };
+    std::fs::write(out, content).wrap_err_with(|| format!("unable to write ABI file `{out}`"))?;
+
+    if !lossy_conversions.is_empty() {
+        eprintln!();
+    }
+    pretty_print::success(&format!(
+        "ABI migrated to schema version {}: `{}`",
+        near_abi::SCHEMA_VERSION,
+        out.to_string().magenta()
+    ));
+    Ok(lossy_conversions)
+}
+
+/// Converts `abi` of any schema version up to the current [`near_abi::SCHEMA_VERSION`],
+/// returning parts of it, which couldn't be converted without loss of information.
+///
+/// Older layouts are recognized by their shape:
+/// - `abi_schema_version` instead of `schema_version`,
+/// - `is_view`/`is_init`/`is_payable`/`is_private` function flags instead of `kind` and `modifiers`,
+/// - list of parameters, each with its own `serialization_type`, instead of `params.args`,
+/// - Borsh schemas in `borsh` 0.x format, before schema version 0.4.0.
+///
+/// Fields, unknown to the current schema, are dropped and reported.
+pub fn migrate(abi: Value) -> eyre::Result<(near_abi::AbiRoot, Vec<LossyConversion>)> {
+    let Value::Object(mut root) = abi else {
+        eyre::bail!("ABI must be a JSON object");
+    };
+    let version = root
+        .remove("schema_version")
+        .or_else(|| root.remove("abi_schema_version"))
+        .wrap_err("ABI has no `schema_version`")?;
+    let version = version
+        .as_str()
+        .and_then(|version| Version::parse(version).ok())
+        .wrap_err_with(|| format!("`schema_version` {version} isn't a valid semver value"))?;
+    let current = Version::parse(near_abi::SCHEMA_VERSION)?;
+    if (version.major, version.minor) > (current.major, current.minor) {
+        eyre::bail!(
+            "ABI schema version {} is newer than {}, supported by this version of cargo-near",
+            version,
+            current
+        );
+    }
+    root.insert("schema_version".into(), json!(near_abi::SCHEMA_VERSION));
+    root.entry("metadata").or_insert_with(|| json!({}));
+
+    let mut migration = Migration {
+        legacy_borsh: version < BORSH_V1_SCHEMA_VERSION,
+        lossy: vec![],
+    };
+    let body = root
+        .get_mut("body")
+        .and_then(Value::as_object_mut)
+        .wrap_err("ABI has no `body` object")?;
+    if let Some(functions) = body.get_mut("functions") {
+        let functions = functions
+            .as_array_mut()
+            .wrap_err("`body.functions` must be an array")?;
+        for function in functions {
+            migration.function(function)?;
+        }
+    }
+    migration.drop_unknown_fields(body, &BODY_KEYS, "body.");
+    migration.drop_unknown_fields(&mut root, &ROOT_KEYS, "");
+
+    let contract_abi = serde_json::from_value(Value::Object(root)).wrap_err_with(|| {
+        format!(
+            "unable to convert ABI of schema version {} to {}",
+            version,
+            near_abi::SCHEMA_VERSION
+        )
+    })?;
+    Ok((contract_abi, migration.lossy))
+}
+
+struct Migration {
+    /// Borsh schemas are in `borsh` 0.x format
+    legacy_borsh: bool,
+    lossy: Vec<LossyConversion>,
+}
+
+impl Migration {
+    fn report(&mut self, path: String, message: impl Into<String>) {
+        self.lossy.push(LossyConversion {
+            path,
+            message: message.into(),
+        });
+    }
+
+    fn drop_unknown_fields(
+        &mut self,
+        object: &mut Map<String, Value>,
+        known: &[&str],
+        prefix: &str,
+    ) {
+        let unknown = object
+            .keys()
+            .filter(|key| !known.contains(&key.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in unknown {
+            object.remove(&key);
+            self.report(
+                format!("{prefix}{key}"),
+                "is unknown to the current schema and was dropped",
+            );
+        }
+    }
+
+    fn function(&mut self, function: &mut Value) -> eyre::Result<()> {
+        let function = function
+            .as_object_mut()
+            .wrap_err("ABI function must be a JSON object")?;
+        let name = function
+            .get("name")
+            .and_then(Value::as_str)
+            .wrap_err("ABI function has no `name`")?
+            .to_string();
+
+        let mut flag = |key: &str| function.remove(key).and_then(|value| value.as_bool());
+        let is_view = flag("is_view");
+        let modifier_flags = [
+            (flag("is_init"), "init"),
+            (flag("is_private"), "private"),
+            (flag("is_payable"), "payable"),
+        ];
+        if let Some(is_view) = is_view {
+            let kind = if is_view { "view" } else { "call" };
+            function.entry("kind").or_insert_with(|| json!(kind));
+        }
+        let modifiers = modifier_flags
+            .into_iter()
+            .filter(|(flag, _)| *flag == Some(true))
+            .map(|(_, modifier)| json!(modifier))
+            .collect::<Vec<_>>();
+        if !modifiers.is_empty() {
+            let existing = function
+                .entry("modifiers")
+                .or_insert_with(|| json!([]))
+                .as_array_mut()
+                .wrap_err_with(|| format!("`{name}.modifiers` must be an array"))?;
+            for modifier in modifiers {
+                if !existing.contains(&modifier) {
+                    existing.push(modifier);
+                }
+            }
+        }
+
+        if let Some(Value::Array(params)) = function.get_mut("params") {
+            let params = std::mem::take(params);
+            match self.params(&name, params)? {
+                Some(params) => function.insert("params".into(), params),
+                None => function.remove("params"),
+            };
+        }
+        if let Some(params) = function.get_mut("params") {
+            if params["serialization_type"] == "borsh" {
+                let args = params["args"].as_array_mut().into_iter().flatten();
+                for arg in args {
+                    let path = format!("{name}.params.{}", arg["name"].as_str().unwrap_or("?"));
+                    self.borsh_schema(&path, &mut arg["type_schema"])?;
+                }
+            }
+        }
+        for key in ["result", "callbacks_vec"] {
+            if let Some(abi_type) = function.get_mut(key) {
+                self.abi_type(&format!("{name}.{key}"), abi_type)?;
+            }
+        }
+        if let Some(Value::Array(callbacks)) = function.get_mut("callbacks") {
+            for (index, abi_type) in callbacks.iter_mut().enumerate() {
+                self.abi_type(&format!("{name}.callbacks[{index}]"), abi_type)?;
+            }
+        }
+
+        self.drop_unknown_fields(function, &FUNCTION_KEYS, &format!("{name}."));
+        Ok(())
+    }
+
+    /// converts a list of parameters, each with its own `serialization_type`,
+    /// to `{ serialization_type, args }` object, or `None` for no parameters
+    fn params(&mut self, name: &str, params: Vec<Value>) -> eyre::Result<Option<Value>> {
+        let mut serialization_type = None;
+        let mut args = vec![];
+        for param in params {
+            let Value::Object(mut param) = param else {
+                eyre::bail!("parameter of `{name}` must be a JSON object");
+            };
+            let param_serialization_type = param
+                .remove("serialization_type")
+                .wrap_err_with(|| format!("parameter of `{name}` has no `serialization_type`"))?;
+            if *serialization_type.get_or_insert_with(|| param_serialization_type.clone())
+                != param_serialization_type
+            {
+                eyre::bail!(
+                    "`{}` mixes parameters of different serialization types, which can't be represented in schema version {}",
+                    name,
+                    near_abi::SCHEMA_VERSION
+                );
+            }
+            args.push(Value::Object(param));
+        }
+        Ok(serialization_type.map(
+            |serialization_type| json!({ "serialization_type": serialization_type, "args": args }),
+        ))
+    }
+
+    fn abi_type(&mut self, path: &str, abi_type: &mut Value) -> eyre::Result<()> {
+        if abi_type["serialization_type"] == "borsh" {
+            self.borsh_schema(path, &mut abi_type["type_schema"])?;
+        }
+        Ok(())
+    }
+
+    /// converts Borsh schema container from `borsh` 0.x format to 1.x one,
+    /// as serialized by [`near_abi::AbiType::Borsh`]
+    fn borsh_schema(&mut self, path: &str, schema: &mut Value) -> eyre::Result<()> {
+        if !self.legacy_borsh {
+            return Ok(());
+        }
+        let definitions = schema
+            .get_mut("definitions")
+            .and_then(Value::as_object_mut)
+            .wrap_err_with(|| format!("Borsh schema of `{path}` has no `definitions`"))?;
+        let mut converted = Map::new();
+        for (name, definition) in std::mem::take(definitions) {
+            let definition = legacy_borsh_definition(&definition).wrap_err_with(|| {
+                format!(
+                    "Borsh schema of `{path}` has unsupported definition of `{name}`: {definition}"
+                )
+            })?;
+            converted.insert(rename_borsh_declaration(&name), definition);
+        }
+
+        let declaration = schema["declaration"]
+            .as_str()
+            .wrap_err_with(|| format!("Borsh schema of `{path}` has no `declaration`"))?;
+        let mut referenced = vec![rename_borsh_declaration(declaration)];
+        for definition in converted.values_mut() {
+            for declaration in borsh_declarations_mut(definition) {
+                if let Some(name) = declaration.as_str() {
+                    let name = rename_borsh_declaration(name);
+                    *declaration = json!(name);
+                    referenced.push(name);
+                }
+            }
+        }
+        // primitives weren't listed in `definitions` of `borsh` 0.x schemas
+        for name in referenced.iter() {
+            if converted.contains_key(name) {
+                continue;
+            }
+            match primitive_borsh_definition(name) {
+                Some(definition) => {
+                    converted.insert(name.clone(), definition);
+                }
+                None => self.report(
+                    path.to_string(),
+                    format!("Borsh schema references `{name}`, which has no definition"),
+                ),
+            }
+        }
+        schema["declaration"] = json!(referenced[0]);
+        schema["definitions"] = Value::Object(converted);
+        Ok(())
+    }
+}
+
+/// `borsh` 0.x definition in `borsh` 1.x format; enums of `borsh` 0.x always had
+/// 1-byte tags with sequential discriminants
+fn legacy_borsh_definition(definition: &Value) -> Option<Value> {
+    let (kind, body) = definition.as_object()?.iter().next()?;
+    let converted = match kind.as_str() {
+        "Array" => json!({ "Sequence": {
+            "length_width": 0,
+            "length_range": { "start": body["length"], "end": body["length"] },
+            "elements": body["elements"],
+        }}),
+        "Sequence" => json!({ "Sequence": {
+            "length_width": 4,
+            "length_range": { "start": 0, "end": u32::MAX },
+            "elements": body["elements"],
+        }}),
+        "Tuple" => json!({ "Tuple": body["elements"] }),
+        "Enum" => {
+            let variants = body["variants"]
+                .as_array()?
+                .iter()
+                .enumerate()
+                .map(|(discriminant, variant)| json!([discriminant, variant[0], variant[1]]))
+                .collect::<Vec<_>>();
+            json!({ "Enum": { "tag_width": 1, "variants": variants } })
+        }
+        "Struct" => match &body["fields"] {
+            Value::Object(fields) => {
+                let fields = fields
+                    .get("NamedFields")
+                    .or_else(|| fields.get("UnnamedFields"))?;
+                json!({ "Struct": fields })
+            }
+            Value::String(fields) if fields == "Empty" => json!({ "Struct": null }),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(converted)
+}
+
+/// declarations, referenced by a `borsh` 1.x definition
+fn borsh_declarations_mut(definition: &mut Value) -> Vec<&mut Value> {
+    let Some((kind, body)) = definition.as_object_mut().and_then(|d| d.iter_mut().next()) else {
+        return vec![];
+    };
+    match kind.as_str() {
+        "Sequence" => vec![&mut body["elements"]],
+        "Tuple" => body.as_array_mut().into_iter().flatten().collect(),
+        "Enum" => body["variants"]
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .map(|variant| &mut variant[2])
+            .collect(),
+        "Struct" => body
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .map(|field| {
+                // named field is a `[name, declaration]` pair
+                if field.is_array() {
+                    &mut field[1]
+                } else {
+                    field
+                }
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// `borsh` 0.x names of primitives, renamed in 1.x
+fn rename_borsh_declaration(name: &str) -> String {
+    match name {
+        "string" => "String".into(),
+        "nil" => "()".into(),
+        other => other.into(),
+    }
+}
+
+fn primitive_borsh_definition(name: &str) -> Option<Value> {
+    let size = match name {
+        "()" => 0,
+        "u8" | "i8" | "bool" => 1,
+        "u16" | "i16" => 2,
+        "u32" | "i32" | "f32" => 4,
+        "u64" | "i64" | "f64" => 8,
+        "u128" | "i128" => 16,
+        "String" => {
+            return Some(json!({ "Sequence": {
+                "length_width": 4,
+                "length_range": { "start": 0, "end": u32::MAX },
+                "elements": "u8",
+            }}));
+        }
+        _ => return None,
+    };
+    Some(json!({ "Primitive": size }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_legacy_function_layout() {
+        let abi = json!({
+            "abi_schema_version": "0.1.0",
+            "metadata": { "name": "contract" },
+            "body": {
+                "functions": [
+                    {
+                        "name": "new",
+                        "is_view": false,
+                        "is_init": true,
+                        "is_private": true,
+                        "params": [
+                            { "name": "owner_id", "serialization_type": "json", "type_schema": { "type": "string" } }
+                        ]
+                    },
+                    {
+                        "name": "get_state",
+                        "is_view": true,
+                        "params": [],
+                        "result": {
+                            "serialization_type": "borsh",
+                            "type_schema": {
+                                "declaration": "State",
+                                "definitions": {
+                                    "State": { "Struct": { "fields": { "NamedFields": [["owner", "string"], ["mode", "Mode"]] } } },
+                                    "Mode": { "Enum": { "variants": [["Active", "nil"], ["Paused", "u64"]] } }
+                                }
+                            }
+                        },
+                        "deprecated": true
+                    }
+                ],
+                "root_schema": {}
+            }
+        });
+
+        let (contract_abi, lossy) = migrate(abi).unwrap();
+
+        assert_eq!(contract_abi.schema_version, near_abi::SCHEMA_VERSION);
+        let new = &contract_abi.body.functions[0];
+        assert_eq!(new.kind, near_abi::AbiFunctionKind::Call);
+        assert_eq!(
+            new.modifiers,
+            vec![
+                near_abi::AbiFunctionModifier::Init,
+                near_abi::AbiFunctionModifier::Private
+            ]
+        );
+        let near_abi::AbiParameters::Json { args } = &new.params else {
+            panic!("expected json parameters");
+        };
+        assert_eq!(args[0].name, "owner_id");
+
+        let get_state = &contract_abi.body.functions[1];
+        assert_eq!(get_state.kind, near_abi::AbiFunctionKind::View);
+        assert!(get_state.params.is_empty());
+        let Some(near_abi::AbiType::Borsh { type_schema }) = &get_state.result else {
+            panic!("expected borsh result");
+        };
+        assert_eq!(
+            type_schema.get_definition("Mode"),
+            Some(&borsh::schema::Definition::Enum {
+                tag_width: 1,
+                variants: vec![
+                    (0, "Active".into(), "()".into()),
+                    (1, "Paused".into(), "u64".into())
+                ],
+            })
+        );
+        assert_eq!(
+            type_schema.get_definition("u64"),
+            Some(&borsh::schema::Definition::Primitive(8))
+        );
+        assert!(type_schema.get_definition("String").is_some());
+
+        assert_eq!(
+            lossy,
+            vec![LossyConversion {
+                path: "get_state.deprecated".into(),
+                message: "is unknown to the current schema and was dropped".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_and_mixed() {
+        let newer = json!({ "schema_version": "0.5.0", "metadata": {}, "body": { "functions": [], "root_schema": {} } });
+        assert!(migrate(newer).is_err());
+
+        let mixed = json!({
+            "schema_version": "0.2.0",
+            "metadata": {},
+            "body": {
+                "functions": [{
+                    "name": "f",
+                    "kind": "call",
+                    "params": [
+                        { "name": "a", "serialization_type": "json", "type_schema": {} },
+                        { "name": "b", "serialization_type": "borsh", "type_schema": { "declaration": "u8", "definitions": {} } }
+                    ]
+                }],
+                "root_schema": {}
+            }
+        });
+        assert!(migrate(mixed).is_err());
+    }
+}
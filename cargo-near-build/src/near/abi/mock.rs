@@ -0,0 +1,800 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+use near_abi::{AbiFunction, AbiFunctionKind, AbiFunctionModifier, AbiParameters, AbiType};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::{Value, json};
+
+use crate::pretty_print;
+
+use super::schema::definition_name;
+
+/// keep in sync with `near-sdk` version of `cargo near new` project template
+const NEAR_SDK_VERSION: &str = "5.29";
+
+/// name of the generated contract state struct
+const STATE_STRUCT: &str = "MockContract";
+
+/// `near-sdk` types, which are referenced by name from ABI definitions, instead of
+/// generating mock types for them
+const NEAR_SDK_TYPES: [(&str, &str); 9] = [
+    ("AccountId", "near_sdk::AccountId"),
+    ("U128", "near_sdk::json_types::U128"),
+    ("U64", "near_sdk::json_types::U64"),
+    ("I128", "near_sdk::json_types::I128"),
+    ("I64", "near_sdk::json_types::I64"),
+    ("Base64VecU8", "near_sdk::json_types::Base64VecU8"),
+    ("PublicKey", "near_sdk::PublicKey"),
+    ("NearToken", "near_sdk::NearToken"),
+    ("NearGas", "near_sdk::Gas"),
+];
+
+const RUST_KEYWORDS: [&str; 51] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+/// Generates a contract crate in `out_dir` with the same public interface as the contract,
+/// described by ABI file at `abi_path`: the same functions with the same modifiers,
+/// and argument and result types, derived from their JSON schemas.
+///
+/// Functions return canned values from `responses` JSON file, which maps function names
+/// to their results, or placeholder values, derived from result schemas.
+/// Functions with Borsh-serialized arguments, callbacks or results are skipped.
+pub fn run(
+    abi_path: &camino::Utf8Path,
+    out_dir: &camino::Utf8Path,
+    responses: Option<&camino::Utf8Path>,
+) -> eyre::Result<()> {
+    if out_dir.exists() {
+        eyre::bail!("Destination `{out_dir}` already exists. Refusing to overwrite it.");
+    }
+    let contract_abi = super::validate::read_abi(abi_path)?;
+    let responses: BTreeMap<String, Value> = match responses {
+        Some(path) => {
+            let content = std::fs::read(path)
+                .wrap_err_with(|| format!("unable to read canned responses file `{path}`"))?;
+            serde_json::from_slice(&content).wrap_err_with(|| {
+                format!(
+                    "unable to parse `{path}` as JSON object of function names to their results"
+                )
+            })?
+        }
+        None => BTreeMap::new(),
+    };
+    let package_name = out_dir
+        .file_name()
+        .wrap_err("Could not extract package name from destination path")?;
+
+    let mock = generate(&contract_abi, package_name, &responses)?;
+    for skipped in mock.skipped.iter() {
+        eprintln!(
+            "{}: `{}` is skipped, as mocking Borsh-serialized arguments, callbacks or results isn't supported",
+            "warning".yellow(),
+            skipped
+        );
+    }
+    std::fs::create_dir_all(out_dir.join("src"))?;
+    std::fs::write(out_dir.join("Cargo.toml"), mock.cargo_toml)?;
+    std::fs::write(out_dir.join("src").join("lib.rs"), mock.lib_rs)?;
+
+    pretty_print::success(&format!(
+        "Mock contract crate generated: `{}`",
+        out_dir.to_string().magenta()
+    ));
+    Ok(())
+}
+
+#[derive(Debug)]
+struct MockCrate {
+    cargo_toml: String,
+    lib_rs: String,
+    /// functions, which couldn't be mocked
+    skipped: Vec<String>,
+}
+
+fn generate(
+    contract_abi: &near_abi::AbiRoot,
+    package_name: &str,
+    responses: &BTreeMap<String, Value>,
+) -> eyre::Result<MockCrate> {
+    for method in responses.keys() {
+        let function = contract_abi
+            .body
+            .functions
+            .iter()
+            .find(|function| &function.name == method)
+            .wrap_err_with(|| format!("canned response for `{method}`, which isn't in the ABI"))?;
+        if function.result.is_none() {
+            eyre::bail!("canned response for `{method}`, which doesn't return a value");
+        }
+    }
+
+    let contract_name = contract_abi
+        .metadata
+        .name
+        .as_deref()
+        .unwrap_or("the contract");
+    let mut generator = Generator {
+        definitions: &contract_abi.body.root_schema.definitions,
+        types: vec![],
+        generated: BTreeSet::new(),
+        in_progress: vec![],
+    };
+    let mut functions = vec![];
+    let mut skipped = vec![];
+    for function in contract_abi.body.functions.iter() {
+        match generator.function(function, responses.get(&function.name))? {
+            Some(source) => functions.push(source),
+            None => skipped.push(function.name.clone()),
+        }
+    }
+
+    let mut lib_rs = format!(
+        "//! Mock of `{contract_name}` contract, generated by `cargo near abi mock` from its ABI.\n\
+         #![allow(unused_variables)]\n\
+         \n\
+         use near_sdk::near;\n\
+         \n\
+         #[near(contract_state)]\n\
+         #[derive(Default)]\n\
+         pub struct {STATE_STRUCT} {{}}\n"
+    );
+    for source in generator.types {
+        lib_rs.push('\n');
+        lib_rs.push_str(&source);
+    }
+    lib_rs.push_str(&format!("\n#[near]\nimpl {STATE_STRUCT} {{\n"));
+    lib_rs.push_str(&functions.join("\n"));
+    lib_rs.push_str("}\n");
+
+    let cargo_toml = format!(
+        r#"[package]
+name = "{package_name}"
+description = "Mock of `{contract_name}` contract, generated by `cargo near abi mock` from its ABI"
+version = "0.1.0"
+edition = "2024"
+
+[lib]
+crate-type = ["cdylib", "rlib"]
+
+[dependencies]
+near-sdk = "{NEAR_SDK_VERSION}"
+
+[profile.release]
+codegen-units = 1
+opt-level = "z"
+lto = true
+debug = false
+panic = "abort"
+overflow-checks = true
+
+[lints.rust.unexpected_cfgs]
+level = "warn"
+check-cfg = ['cfg(near)']
+"#
+    );
+    Ok(MockCrate {
+        cargo_toml,
+        lib_rs,
+        skipped,
+    })
+}
+
+struct Generator<'a> {
+    definitions: &'a schemars::Map<String, Schema>,
+    /// sources of types, generated for ABI definitions
+    types: Vec<String>,
+    generated: BTreeSet<String>,
+    /// definitions, sources of which are being generated, references to them are boxed
+    in_progress: Vec<String>,
+}
+
+impl Generator<'_> {
+    /// source of mocked `function`, or `None` if it can't be mocked
+    fn function(
+        &mut self,
+        function: &AbiFunction,
+        response: Option<&Value>,
+    ) -> eyre::Result<Option<String>> {
+        let args = match &function.params {
+            AbiParameters::Json { args } => args,
+            AbiParameters::Borsh { .. } => return Ok(None),
+        };
+        let result = match &function.result {
+            Some(AbiType::Json { type_schema }) => Some(type_schema),
+            Some(AbiType::Borsh { .. }) => return Ok(None),
+            None => None,
+        };
+        let mut callbacks = vec![];
+        for callback in function.callbacks.iter() {
+            match callback {
+                AbiType::Json { type_schema } => callbacks.push(type_schema),
+                AbiType::Borsh { .. } => return Ok(None),
+            }
+        }
+        let callbacks_vec = match &function.callbacks_vec {
+            Some(AbiType::Json { type_schema }) => Some(type_schema),
+            Some(AbiType::Borsh { .. }) => return Ok(None),
+            None => None,
+        };
+        let is_init = function.modifiers.contains(&AbiFunctionModifier::Init);
+
+        let mut source = String::new();
+        for line in function.doc.iter().flat_map(|doc| doc.lines()) {
+            source.push_str(&format!("    ///{}\n", doc_line(line)));
+        }
+        for modifier in function.modifiers.iter() {
+            let attribute = match modifier {
+                AbiFunctionModifier::Init => "init",
+                AbiFunctionModifier::Private => "private",
+                AbiFunctionModifier::Payable => "payable",
+            };
+            source.push_str(&format!("    #[{attribute}]\n"));
+        }
+        let mut params = vec![];
+        if !is_init {
+            params.push(match function.kind {
+                AbiFunctionKind::View => "&self".to_string(),
+                AbiFunctionKind::Call => "&mut self".to_string(),
+            });
+        }
+        for arg in args.iter() {
+            let ident = rust_ident(&arg.name);
+            if ident.trim_start_matches("r#") != arg.name {
+                eyre::bail!(
+                    "argument `{}` of `{}` isn't a valid Rust identifier",
+                    arg.name,
+                    function.name
+                );
+            }
+            params.push(format!("{ident}: {}", self.rust_type(&arg.type_schema)));
+        }
+        // ABI doesn't keep names of callback parameters
+        for (index, callback) in callbacks.into_iter().enumerate() {
+            params.push(format!(
+                "#[callback_unwrap] callback_{index}: {}",
+                self.rust_type(callback)
+            ));
+        }
+        if let Some(callbacks_vec) = callbacks_vec {
+            params.push(format!(
+                "#[callback_vec] callbacks: Vec<{}>",
+                self.rust_type(callbacks_vec)
+            ));
+        }
+        let signature = format!(
+            "    pub fn {}({})",
+            rust_ident(&function.name),
+            params.join(", ")
+        );
+
+        let body = if is_init {
+            source.push_str(&format!("{signature} -> Self {{\n"));
+            "        Self {}\n".to_string()
+        } else if let Some(result) = result {
+            source.push_str(&format!("{signature} -> {} {{\n", self.rust_type(result)));
+            let value = match response {
+                Some(value) => value.clone(),
+                None => self.placeholder(result, &mut BTreeSet::new()),
+            };
+            format!(
+                "        near_sdk::serde_json::from_str({})\n            .expect(\"canned result of `{}` matches its type\")\n",
+                raw_string(&serde_json::to_string(&value)?),
+                function.name
+            )
+        } else {
+            source.push_str(&format!("{signature} {{\n"));
+            String::new()
+        };
+        source.push_str(&body);
+        source.push_str("    }\n");
+        Ok(Some(source))
+    }
+
+    /// Rust type of values, described by `schema`, falling back to `serde_json::Value`
+    fn rust_type(&mut self, schema: &Schema) -> String {
+        const ANY: &str = "near_sdk::serde_json::Value";
+        let Schema::Object(object) = schema else {
+            return ANY.to_string();
+        };
+        if let Some(name) = object.reference.as_deref().and_then(definition_name) {
+            return self.definition_type(name);
+        }
+        if let Some(inner) = nullable_subschema(object) {
+            return format!("Option<{}>", self.rust_type(inner));
+        }
+        let instance_type = match &object.instance_type {
+            Some(SingleOrVec::Single(instance_type)) => **instance_type,
+            Some(SingleOrVec::Vec(types)) => {
+                let non_null = types
+                    .iter()
+                    .filter(|ty| **ty != InstanceType::Null)
+                    .collect::<Vec<_>>();
+                if let ([single], true) = (non_null.as_slice(), types.contains(&InstanceType::Null))
+                {
+                    let inner = Schema::Object(SchemaObject {
+                        instance_type: Some((**single).into()),
+                        ..object.clone()
+                    });
+                    return format!("Option<{}>", self.rust_type(&inner));
+                }
+                return ANY.to_string();
+            }
+            None => return ANY.to_string(),
+        };
+        match instance_type {
+            InstanceType::Boolean => "bool".to_string(),
+            InstanceType::String => "String".to_string(),
+            InstanceType::Null => "()".to_string(),
+            InstanceType::Integer => match object.format.as_deref() {
+                Some("uint8") => "u8",
+                Some("uint16") => "u16",
+                Some("uint32") => "u32",
+                Some("uint64") => "u64",
+                Some("uint128") => "u128",
+                Some("uint") => "u64",
+                Some("int8") => "i8",
+                Some("int16") => "i16",
+                Some("int32") => "i32",
+                Some("int128") => "i128",
+                _ => "i64",
+            }
+            .to_string(),
+            InstanceType::Number => match object.format.as_deref() {
+                Some("float") => "f32".to_string(),
+                _ => "f64".to_string(),
+            },
+            InstanceType::Array => {
+                match object.array.as_ref().and_then(|array| array.items.as_ref()) {
+                    Some(SingleOrVec::Single(item)) => format!("Vec<{}>", self.rust_type(item)),
+                    Some(SingleOrVec::Vec(items)) => {
+                        let items = items
+                            .iter()
+                            .map(|item| self.rust_type(item))
+                            .collect::<Vec<_>>();
+                        format!("({},)", items.join(", "))
+                    }
+                    None => format!("Vec<{ANY}>"),
+                }
+            }
+            InstanceType::Object => match object.object.as_ref() {
+                Some(validation) if validation.properties.is_empty() => {
+                    match validation.additional_properties.as_deref() {
+                        Some(value @ Schema::Object(_)) => format!(
+                            "std::collections::HashMap<String, {}>",
+                            self.rust_type(value)
+                        ),
+                        _ => ANY.to_string(),
+                    }
+                }
+                _ => ANY.to_string(),
+            },
+        }
+    }
+
+    /// Rust type for ABI definition `name`, generating its source on first use
+    fn definition_type(&mut self, name: &str) -> String {
+        if let Some((_, path)) = NEAR_SDK_TYPES.iter().find(|(known, _)| *known == name) {
+            return path.to_string();
+        }
+        let ident = type_ident(name);
+        if !self.generated.insert(name.to_string()) {
+            // e.g. `Option<Self>` field, which would make the type infinitely sized
+            if self
+                .in_progress
+                .iter()
+                .any(|in_progress| in_progress == name)
+            {
+                return format!("Box<{ident}>");
+            }
+            return ident;
+        }
+        let Some(Schema::Object(definition)) = self.definitions.get(name) else {
+            self.types
+                .push(format!("pub type {ident} = near_sdk::serde_json::Value;\n"));
+            return ident;
+        };
+        self.in_progress.push(name.to_string());
+        let source = if let Some(properties) = definition
+            .object
+            .as_ref()
+            .filter(|validation| !validation.properties.is_empty())
+        {
+            let mut fields = String::new();
+            for (property, schema) in properties.properties.iter() {
+                let field = rust_ident(&to_snake_case(property));
+                if field.trim_start_matches("r#") != property {
+                    fields.push_str(&format!("    #[serde(rename = \"{property}\")]\n"));
+                }
+                let mut rust_type = self.rust_type(schema);
+                if !properties.required.contains(property) {
+                    if !rust_type.starts_with("Option<") {
+                        rust_type = format!("Option<{rust_type}>");
+                    }
+                    fields.push_str(
+                        "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                    );
+                }
+                fields.push_str(&format!("    pub {field}: {rust_type},\n"));
+            }
+            format!("#[near(serializers = [json])]\npub struct {ident} {{\n{fields}}}\n")
+        } else if let Some(values) = definition
+            .enum_values
+            .as_ref()
+            .filter(|values| !values.is_empty() && values.iter().all(Value::is_string))
+        {
+            let mut variants = String::new();
+            for value in values.iter().filter_map(Value::as_str) {
+                let variant = type_ident(value);
+                if variant != value {
+                    variants.push_str(&format!("    #[serde(rename = \"{value}\")]\n"));
+                }
+                variants.push_str(&format!("    {variant},\n"));
+            }
+            format!("#[near(serializers = [json])]\npub enum {ident} {{\n{variants}}}\n")
+        } else {
+            let rust_type = self.rust_type(&Schema::Object(definition.clone()));
+            format!("pub type {ident} = {rust_type};\n")
+        };
+        self.in_progress.pop();
+        let source = match definition
+            .metadata
+            .as_ref()
+            .and_then(|m| m.description.as_ref())
+        {
+            Some(description) => {
+                let doc = description
+                    .lines()
+                    .map(|line| format!("///{}\n", doc_line(line)))
+                    .collect::<String>();
+                format!("{doc}{source}")
+            }
+            None => source,
+        };
+        self.types.push(source);
+        ident
+    }
+
+    /// a value, matching `schema`, returned by functions without a canned response
+    fn placeholder(&self, schema: &Schema, visiting: &mut BTreeSet<String>) -> Value {
+        let Schema::Object(object) = schema else {
+            return Value::Null;
+        };
+        if let Some(name) = object.reference.as_deref().and_then(definition_name) {
+            match name {
+                "AccountId" => return json!("mock.near"),
+                "PublicKey" => return json!("ed25519:11111111111111111111111111111111"),
+                "Base64VecU8" => return json!(""),
+                _ => {}
+            }
+            let Some(definition) = self.definitions.get(name) else {
+                return Value::Null;
+            };
+            // recursive types are cut at the first repetition
+            if !visiting.insert(name.to_string()) {
+                return Value::Null;
+            }
+            let value = self.placeholder(definition, visiting);
+            visiting.remove(name);
+            return value;
+        }
+        if nullable_subschema(object).is_some() {
+            return Value::Null;
+        }
+        if let Some(value) = object.const_value.clone().or_else(|| {
+            object
+                .enum_values
+                .as_ref()
+                .and_then(|values| values.first().cloned())
+        }) {
+            return value;
+        }
+        if let Some(first) = object
+            .subschemas
+            .as_ref()
+            .and_then(|subschemas| subschemas.one_of.as_ref().or(subschemas.any_of.as_ref()))
+            .and_then(|variants| variants.first())
+        {
+            return self.placeholder(first, visiting);
+        }
+        let instance_type = match &object.instance_type {
+            Some(SingleOrVec::Single(instance_type)) => **instance_type,
+            Some(SingleOrVec::Vec(types)) if types.contains(&InstanceType::Null) => {
+                return Value::Null;
+            }
+            Some(SingleOrVec::Vec(types)) => match types.first() {
+                Some(instance_type) => *instance_type,
+                None => return Value::Null,
+            },
+            None => return Value::Null,
+        };
+        match instance_type {
+            InstanceType::Null => Value::Null,
+            InstanceType::Boolean => json!(false),
+            InstanceType::Integer | InstanceType::Number => json!(0),
+            // `U128`-like wrappers are strings of decimal numbers
+            InstanceType::String => json!("0"),
+            InstanceType::Array => {
+                match object.array.as_ref().and_then(|array| array.items.as_ref()) {
+                    Some(SingleOrVec::Vec(items)) => Value::Array(
+                        items
+                            .iter()
+                            .map(|item| self.placeholder(item, visiting))
+                            .collect(),
+                    ),
+                    _ => json!([]),
+                }
+            }
+            InstanceType::Object => {
+                let mut value = serde_json::Map::new();
+                if let Some(validation) = object.object.as_ref() {
+                    for required in validation.required.iter() {
+                        if let Some(schema) = validation.properties.get(required) {
+                            value.insert(required.clone(), self.placeholder(schema, visiting));
+                        }
+                    }
+                }
+                Value::Object(value)
+            }
+        }
+    }
+}
+
+/// `T` of `Option<T>` schema, which `schemars` emits as `anyOf: [T, null]`
+fn nullable_subschema(object: &SchemaObject) -> Option<&Schema> {
+    let any_of = object.subschemas.as_ref()?.any_of.as_ref()?;
+    let is_null = |schema: &Schema| matches!(schema, Schema::Object(SchemaObject { instance_type: Some(SingleOrVec::Single(ty)), .. }) if **ty == InstanceType::Null);
+    match any_of.as_slice() {
+        [inner, null] | [null, inner] if is_null(null) && !is_null(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// `line` of a doc comment, separated from `///` with a space
+fn doc_line(line: &str) -> String {
+    if line.is_empty() || line.starts_with(' ') {
+        line.to_string()
+    } else {
+        format!(" {line}")
+    }
+}
+
+/// raw string literal with enough `#`-s to contain `content`
+fn raw_string(content: &str) -> String {
+    let hashes = "#".repeat(
+        (1..)
+            .find(|count| !content.contains(&format!("\"{}", "#".repeat(*count))))
+            .unwrap_or(1),
+    );
+    format!("r{hashes}\"{content}\"{hashes}")
+}
+
+/// `name` with characters, not allowed in identifiers, replaced with `_`,
+/// and keywords escaped as raw identifiers
+fn rust_ident(name: &str) -> String {
+    let mut ident = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    ident
+}
+
+/// `UpperCamelCase` identifier for a type or enum variant `name`
+fn type_ident(name: &str) -> String {
+    let ident = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+    rust_ident(&ident)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if index > 0 && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root() -> near_abi::AbiRoot {
+        serde_json::from_value(json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": { "name": "oracle" },
+            "body": {
+                "functions": [
+                    {
+                        "name": "new",
+                        "kind": "call",
+                        "modifiers": ["init", "private"],
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [{ "name": "owner_id", "type_schema": { "$ref": "#/definitions/AccountId" } }]
+                        }
+                    },
+                    {
+                        "name": "get_price",
+                        "doc": " Latest price of `asset`",
+                        "kind": "view",
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [{ "name": "asset", "type_schema": { "$ref": "#/definitions/Asset" } }]
+                        },
+                        "result": {
+                            "serialization_type": "json",
+                            "type_schema": {
+                                "anyOf": [{ "$ref": "#/definitions/Price" }, { "type": "null" }]
+                            }
+                        }
+                    },
+                    {
+                        "name": "report",
+                        "kind": "call",
+                        "modifiers": ["payable"],
+                        "params": {
+                            "serialization_type": "borsh",
+                            "args": [{ "name": "data", "type_schema": { "declaration": "u8", "definitions": { "u8": { "Primitive": 1 } } } }]
+                        }
+                    }
+                ],
+                "root_schema": {
+                    "definitions": {
+                        "AccountId": { "type": "string" },
+                        "Asset": { "type": "string", "enum": ["near", "usd-coin"] },
+                        "Price": {
+                            "type": "object",
+                            "required": ["multiplier", "decimals"],
+                            "properties": {
+                                "multiplier": { "$ref": "#/definitions/U128" },
+                                "decimals": { "type": "integer", "format": "uint8" },
+                                "updatedAt": { "type": ["integer", "null"], "format": "uint64" }
+                            }
+                        },
+                        "U128": { "type": "string" }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_generate_mock() {
+        let responses = BTreeMap::from([(
+            "get_price".to_string(),
+            json!({ "multiplier": "1000", "decimals": 4 }),
+        )]);
+        let mock = generate(&abi_root(), "oracle-mock", &responses).unwrap();
+
+        assert!(mock.cargo_toml.contains("name = \"oracle-mock\""));
+        assert_eq!(mock.skipped, vec!["report"]);
+        let lib_rs = mock.lib_rs;
+        assert!(lib_rs.contains(
+            "    #[init]\n    #[private]\n    pub fn new(owner_id: near_sdk::AccountId) -> Self {\n        Self {}\n    }\n"
+        ));
+        assert!(lib_rs.contains(
+            "    /// Latest price of `asset`\n    pub fn get_price(&self, asset: Asset) -> Option<Price> {\n        near_sdk::serde_json::from_str(r#\"{\"decimals\":4,\"multiplier\":\"1000\"}\"#)\n"
+        ));
+        assert!(lib_rs.contains(
+            "pub enum Asset {\n    #[serde(rename = \"near\")]\n    Near,\n    #[serde(rename = \"usd-coin\")]\n    UsdCoin,\n}\n"
+        ));
+        assert!(lib_rs.contains("    pub multiplier: near_sdk::json_types::U128,\n"));
+        assert!(lib_rs.contains(
+            "    #[serde(rename = \"updatedAt\")]\n    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    pub updated_at: Option<u64>,\n"
+        ));
+    }
+
+    #[test]
+    fn test_placeholder_and_unknown_response() {
+        let contract_abi = abi_root();
+        let generator = Generator {
+            definitions: &contract_abi.body.root_schema.definitions,
+            types: vec![],
+            generated: BTreeSet::new(),
+            in_progress: vec![],
+        };
+        let price = Schema::Object(SchemaObject {
+            reference: Some("#/definitions/Price".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            generator.placeholder(&price, &mut BTreeSet::new()),
+            json!({ "multiplier": "0", "decimals": 0 })
+        );
+
+        let responses = BTreeMap::from([("get_owner".to_string(), json!("alice.near"))]);
+        assert!(generate(&contract_abi, "oracle-mock", &responses).is_err());
+    }
+
+    #[test]
+    fn test_recursive_types_and_callbacks() {
+        let contract_abi: near_abi::AbiRoot = serde_json::from_value(json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": {},
+            "body": {
+                "functions": [
+                    {
+                        "name": "on_fetched",
+                        "kind": "call",
+                        "modifiers": ["private"],
+                        "params": {
+                            "serialization_type": "json",
+                            "args": [{ "name": "key", "type_schema": { "type": "string" } }]
+                        },
+                        "callbacks": [
+                            { "serialization_type": "json", "type_schema": { "$ref": "#/definitions/Node" } },
+                            { "serialization_type": "json", "type_schema": { "type": "integer", "format": "uint32" } }
+                        ],
+                        "callbacks_vec": { "serialization_type": "json", "type_schema": { "type": "string" } }
+                    },
+                    {
+                        "name": "on_borsh",
+                        "kind": "call",
+                        "callbacks": [
+                            { "serialization_type": "borsh", "type_schema": { "declaration": "u8", "definitions": { "u8": { "Primitive": 1 } } } }
+                        ]
+                    }
+                ],
+                "root_schema": {
+                    "definitions": {
+                        "Node": {
+                            "type": "object",
+                            "required": ["children", "value"],
+                            "properties": {
+                                "value": { "type": "string" },
+                                "next": { "anyOf": [{ "$ref": "#/definitions/Node" }, { "type": "null" }] },
+                                "children": { "type": "array", "items": { "$ref": "#/definitions/Node" } },
+                                "tag": { "$ref": "#/definitions/Tag" }
+                            }
+                        },
+                        "Tag": {
+                            "type": "object",
+                            "required": ["owner"],
+                            "properties": { "owner": { "$ref": "#/definitions/Node" } }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let mock = generate(&contract_abi, "tree-mock", &BTreeMap::new()).unwrap();
+
+        assert_eq!(mock.skipped, vec!["on_borsh"]);
+        let lib_rs = mock.lib_rs;
+        assert!(lib_rs.contains(
+            "    pub fn on_fetched(&mut self, key: String, #[callback_unwrap] callback_0: Node, #[callback_unwrap] callback_1: u32, #[callback_vec] callbacks: Vec<String>) {\n"
+        ));
+        assert!(lib_rs.contains("    pub next: Option<Box<Node>>,\n"));
+        assert!(lib_rs.contains("    pub children: Vec<Box<Node>>,\n"));
+        assert!(lib_rs.contains("    pub tag: Option<Tag>,\n"));
+        assert!(lib_rs.contains("    pub owner: Box<Node>,\n"));
+    }
+}
@@ -1,9 +1,54 @@
 use crate::types::cargo::metadata::CrateMetadata;
 use crate::types::near::{OutputPaths, abi as abi_types};
 
+#[cfg(feature = "docker")]
+pub mod compat;
+pub(crate) mod filter;
 pub mod generate;
+pub mod lint;
+pub mod migrate;
+pub mod mock;
+pub(crate) mod schema;
+pub mod snapshot;
+pub mod validate;
 
 pub fn build(args: abi_types::Opts) -> eyre::Result<camino::Utf8PathBuf> {
+    use crate::pretty_print;
+    use colored::Colorize;
+
+    let out_dir = args.out_dir.clone();
+    let compact_abi = args.compact_abi;
+    let (crate_metadata, contract_abi) = collect_and_generate(args)?;
+
+    let out_dir = crate_metadata
+        .get_legacy_cargo_near_output_path(out_dir)?
+        .get_out_dir()
+        .clone();
+
+    let format = if compact_abi {
+        abi_types::Format::JsonMin
+    } else {
+        abi_types::Format::Json
+    };
+    let abi_types::Result { path } = write_to_file(
+        &contract_abi,
+        &crate_metadata,
+        format,
+        abi_types::Compression::NoOp,
+    )?;
+
+    let abi_path = crate::fs::copy(&path, &out_dir)?;
+
+    pretty_print::success("ABI Successfully Generated!");
+    eprintln!("     - ABI: {}", abi_path.to_string().yellow().bold());
+
+    Ok(abi_path)
+}
+
+/// collects metadata of the contract's crate and generates its ABI, as specified by `args`
+pub(crate) fn collect_and_generate(
+    args: abi_types::Opts,
+) -> eyre::Result<(CrateMetadata, near_abi::AbiRoot)> {
     // imports #[cfg(feature = "abi_build")]
     use crate::{
         pretty_print,
@@ -13,7 +58,6 @@ pub fn build(args: abi_types::Opts) -> eyre::Result<camino::Utf8PathBuf> {
         },
     };
     use camino::Utf8PathBuf;
-    use colored::Colorize;
 
     let color = args.color.unwrap_or(ColorPreference::Auto);
     color.apply();
@@ -28,46 +72,28 @@ pub fn build(args: abi_types::Opts) -> eyre::Result<camino::Utf8PathBuf> {
         CrateMetadata::collect(manifest_path, args.no_locked, &CargoTargetDir::NoOp, None)
     })?;
 
-    let out_dir = crate_metadata
-        .get_legacy_cargo_near_output_path(args.out_dir)?
-        .get_out_dir()
-        .clone();
-
-    let cargo_feature_args = {
-        let mut feat_args = vec![];
-        if let Some(features) = args.features.as_deref() {
-            feat_args.extend_from_slice(&["--features", features]);
-        }
-        feat_args
-    };
+    let cargo_feature_args = cargo_feature_args(args.features.as_deref());
+    let filter = abi_types::filter::Filter::new(&args.abi_include, &args.abi_exclude)?;
 
-    let format = if args.compact_abi {
-        abi_types::Format::JsonMin
-    } else {
-        abi_types::Format::Json
-    };
     let contract_abi = generate::procedure(
         &crate_metadata,
         args.no_locked,
         !args.no_doc,
         false,
         &cargo_feature_args,
+        &filter,
         &[],
         color,
     )?;
-    let abi_types::Result { path } = write_to_file(
-        &contract_abi,
-        &crate_metadata,
-        format,
-        abi_types::Compression::NoOp,
-    )?;
-
-    let abi_path = crate::fs::copy(&path, &out_dir)?;
-
-    pretty_print::success("ABI Successfully Generated!");
-    eprintln!("     - ABI: {}", abi_path.to_string().yellow().bold());
+    Ok((crate_metadata, contract_abi))
+}
 
-    Ok(abi_path)
+fn cargo_feature_args(features: Option<&str>) -> Vec<&str> {
+    let mut feat_args = vec![];
+    if let Some(features) = features {
+        feat_args.extend_from_slice(&["--features", features]);
+    }
+    feat_args
 }
 
 pub fn write_to_file(
@@ -0,0 +1,119 @@
+//! helpers for walking JSON schemas of [`near_abi::AbiRoot`], emitted by `schemars`
+
+use std::collections::{BTreeSet, VecDeque};
+
+use schemars::schema::{Schema, SchemaObject, SingleOrVec};
+
+const DEFINITIONS_PREFIX: &str = "#/definitions/";
+
+/// name of definition in `root_schema.definitions`, pointed to by `$ref`
+pub(crate) fn definition_name(reference: &str) -> Option<&str> {
+    reference.strip_prefix(DEFINITIONS_PREFIX)
+}
+
+/// all direct subschemas of a schema object, including ones in
+/// `allOf`/`anyOf`/`oneOf`, array items and object properties
+pub(crate) fn children(object: &SchemaObject) -> Vec<&Schema> {
+    let mut result = vec![];
+    if let Some(subschemas) = &object.subschemas {
+        for list in [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of]
+            .into_iter()
+            .flatten()
+        {
+            result.extend(list.iter());
+        }
+        for single in [
+            &subschemas.not,
+            &subschemas.if_schema,
+            &subschemas.then_schema,
+            &subschemas.else_schema,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            result.push(single.as_ref());
+        }
+    }
+    if let Some(array) = &object.array {
+        match &array.items {
+            Some(SingleOrVec::Single(item)) => result.push(item.as_ref()),
+            Some(SingleOrVec::Vec(items)) => result.extend(items.iter()),
+            None => {}
+        }
+        for single in [&array.additional_items, &array.contains]
+            .into_iter()
+            .flatten()
+        {
+            result.push(single.as_ref());
+        }
+    }
+    if let Some(object_validation) = &object.object {
+        result.extend(object_validation.properties.values());
+        result.extend(object_validation.pattern_properties.values());
+        for single in [
+            &object_validation.additional_properties,
+            &object_validation.property_names,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            result.push(single.as_ref());
+        }
+    }
+    result
+}
+
+/// names of definitions, directly referenced by `schema` or any of its subschemas,
+/// not following the references themselves
+pub(crate) fn direct_references(schema: &Schema) -> BTreeSet<String> {
+    let mut result = BTreeSet::new();
+    let mut queue = VecDeque::from([schema]);
+    while let Some(schema) = queue.pop_front() {
+        let Schema::Object(object) = schema else {
+            continue;
+        };
+        if let Some(name) = object.reference.as_deref().and_then(definition_name) {
+            result.insert(name.to_string());
+        }
+        queue.extend(children(object));
+    }
+    result
+}
+
+/// names of all definitions, transitively reachable from `roots`
+pub(crate) fn reachable_definitions<'a>(
+    root_schema: &schemars::schema::RootSchema,
+    roots: impl IntoIterator<Item = &'a Schema>,
+) -> BTreeSet<String> {
+    let mut reachable = BTreeSet::new();
+    let mut queue: VecDeque<String> = roots.into_iter().flat_map(direct_references).collect();
+    while let Some(name) = queue.pop_front() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+        if let Some(definition) = root_schema.definitions.get(&name) {
+            queue.extend(direct_references(definition));
+        }
+    }
+    reachable
+}
+
+/// json schemas of params, callbacks and result of a function,
+/// Borsh-serialized ones are skipped
+pub(crate) fn function_json_schemas(function: &near_abi::AbiFunction) -> Vec<&Schema> {
+    let mut result = vec![];
+    if let near_abi::AbiParameters::Json { args } = &function.params {
+        result.extend(args.iter().map(|arg| &arg.type_schema));
+    }
+    let abi_types = function
+        .callbacks
+        .iter()
+        .chain(function.callbacks_vec.iter())
+        .chain(function.result.iter());
+    for abi_type in abi_types {
+        if let near_abi::AbiType::Json { type_schema } = abi_type {
+            result.push(type_schema);
+        }
+    }
+    result
+}
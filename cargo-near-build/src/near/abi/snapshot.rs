@@ -0,0 +1,212 @@
+use colored::Colorize;
+use eyre::WrapErr;
+use serde_json::Value;
+
+use crate::pretty_print;
+use crate::types::near::abi as abi_types;
+
+/// number of unchanged lines, printed around each changed region of the diff
+const DIFF_CONTEXT: usize = 3;
+
+/// Generates ABI like [`super::build`] does and compares it with committed ABI file at `path`,
+/// ignoring `wasm_hash` and `build` info, which depend on the build rather than the source.
+///
+/// Returns an error with a diff if they differ, unless `bless` is set, in which case `path`
+/// is overwritten with the generated ABI.
+pub fn check(args: abi_types::Opts, path: &camino::Utf8Path, bless: bool) -> eyre::Result<()> {
+    let compact_abi = args.compact_abi;
+    let (_crate_metadata, contract_abi) = super::collect_and_generate(args)?;
+
+    let generated = normalize(serde_json::to_value(&contract_abi)?);
+    let committed = if path.is_file() {
+        let content =
+            std::fs::read(path).wrap_err_with(|| format!("unable to read ABI file `{path}`"))?;
+        let committed: Value = serde_json::from_slice(&content)
+            .wrap_err_with(|| format!("unable to parse ABI file `{path}`"))?;
+        Some(normalize(committed))
+    } else {
+        None
+    };
+
+    if committed.as_ref() == Some(&generated) {
+        pretty_print::success(&format!("ABI matches `{}`", path.to_string().magenta()));
+        return Ok(());
+    }
+    if bless {
+        let content = if compact_abi {
+            serde_json::to_vec(&contract_abi)?
+        } else {
+            serde_json::to_vec_pretty(&contract_abi)?
+        };
+        std::fs::write(path, content)
+            .wrap_err_with(|| format!("unable to write ABI file `{path}`"))?;
+        pretty_print::success(&format!("ABI written to `{}`", path.to_string().magenta()));
+        return Ok(());
+    }
+    let Some(committed) = committed else {
+        return Err(eyre::eyre!(
+            "ABI file `{}` doesn't exist, run with `--bless` to create it",
+            path
+        ));
+    };
+    eprintln!(
+        "{}",
+        diff(
+            &serde_json::to_string_pretty(&committed)?,
+            &serde_json::to_string_pretty(&generated)?,
+        )
+    );
+    Err(eyre::eyre!(
+        "generated ABI differs from `{}`, run with `--bless` to update it",
+        path
+    ))
+}
+
+/// removes fields of ABI metadata, which vary between builds of the same source
+fn normalize(mut abi: Value) -> Value {
+    if let Some(metadata) = abi.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.remove("wasm_hash");
+        metadata.remove("build");
+    }
+    abi
+}
+
+/// line diff of `previous` and `current` texts with a few lines of context around changes,
+/// in a form, similar to `diff -u`
+fn diff(previous: &str, current: &str) -> String {
+    let previous = previous.lines().collect::<Vec<_>>();
+    let current = current.lines().collect::<Vec<_>>();
+    let ops = diff_ops(&previous, &current);
+
+    let mut output = vec![];
+    let mut last_printed = None;
+    for (index, op) in ops.iter().enumerate() {
+        let window = index.saturating_sub(DIFF_CONTEXT)..(index + DIFF_CONTEXT + 1).min(ops.len());
+        let near_change = ops[window].iter().any(|op| !matches!(op, DiffOp::Same(..)));
+        if !near_change {
+            continue;
+        }
+        if last_printed.is_some_and(|last| last + 1 != index) {
+            output.push("...".dimmed().to_string());
+        }
+        last_printed = Some(index);
+        output.push(match op {
+            DiffOp::Same(line) => format!(" {line}"),
+            DiffOp::Removed(line) => format!("-{line}").red().to_string(),
+            DiffOp::Added(line) => format!("+{line}").green().to_string(),
+        });
+    }
+    output.join("\n")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DiffOp<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// longest common subsequence based diff; common prefix and suffix are trimmed first,
+/// as ABI changes are usually local
+fn diff_ops<'a>(previous: &[&'a str], current: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let prefix = previous
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &previous[prefix..previous.len() - suffix];
+    let new = &current[prefix..current.len() - suffix];
+
+    // lcs[i][j] is length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = previous[..prefix]
+        .iter()
+        .map(|line| DiffOp::Same(line))
+        .collect::<Vec<_>>();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push(DiffOp::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(DiffOp::Removed(old[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(
+        previous[previous.len() - suffix..]
+            .iter()
+            .map(|line| DiffOp::Same(line)),
+    );
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ignores_build_info() {
+        let committed = serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": {
+                "name": "contract",
+                "wasm_hash": "8cKzk1hD5EB2bJ4E1Us9Af2TbHiaVZPvh4KfZ9GxSQMa",
+                "build": { "compiler": "rustc 1.86.0", "builder": "cargo-near cargo-near-0.14.0" }
+            },
+            "body": { "functions": [] }
+        });
+        let generated = serde_json::json!({
+            "schema_version": "0.4.0",
+            "metadata": { "name": "contract" },
+            "body": { "functions": [] }
+        });
+        assert_eq!(normalize(committed), normalize(generated));
+    }
+
+    #[test]
+    fn test_diff_ops() {
+        let previous = ["{", "  \"a\": 1,", "  \"b\": 2,", "  \"c\": 3", "}"];
+        let current = [
+            "{",
+            "  \"a\": 1,",
+            "  \"b\": 4,",
+            "  \"c\": 3,",
+            "  \"d\": 5",
+            "}",
+        ];
+        assert_eq!(
+            diff_ops(&previous, &current),
+            vec![
+                DiffOp::Same("{"),
+                DiffOp::Same("  \"a\": 1,"),
+                DiffOp::Removed("  \"b\": 2,"),
+                DiffOp::Removed("  \"c\": 3"),
+                DiffOp::Added("  \"b\": 4,"),
+                DiffOp::Added("  \"c\": 3,"),
+                DiffOp::Added("  \"d\": 5"),
+                DiffOp::Same("}"),
+            ]
+        );
+    }
+}
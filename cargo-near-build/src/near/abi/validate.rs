@@ -0,0 +1,818 @@
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+use near_abi::AbiParameters;
+use schemars::schema::{
+    ArrayValidation, InstanceType, ObjectValidation, RootSchema, Schema, SchemaObject, SingleOrVec,
+    StringValidation,
+};
+use serde_json::Value;
+
+use crate::pretty_print;
+use crate::types::near::abi as abi_types;
+use crate::types::near::abi::validate::{Severity, ValidationError};
+
+use super::schema::definition_name;
+
+/// Validates `json_args` of a call to `method` against contract's ABI, read from `abi_file`,
+/// or generated for the crate, specified by `args`, if `abi_file` is `None`.
+///
+/// Returns an error if arguments don't match the ABI.
+pub fn run(
+    args: abi_types::Opts,
+    abi_file: Option<camino::Utf8PathBuf>,
+    method: &str,
+    json_args: &str,
+) -> eyre::Result<()> {
+    let contract_abi = match abi_file {
+        Some(path) => {
+            args.color
+                .unwrap_or(crate::types::near::build::input::ColorPreference::Auto)
+                .apply();
+            pretty_print::handle_step(&format!("Reading ABI from `{}`...", path), || {
+                read_abi(&path)
+            })?
+        }
+        None => super::collect_and_generate(args)?.1,
+    };
+    check(&contract_abi, method, json_args)
+}
+
+/// Reads ABI from a `*.json` file, or a zstd-compressed `*.zst` one, as written by `cargo near build`
+pub fn read_abi(path: &camino::Utf8Path) -> eyre::Result<near_abi::AbiRoot> {
+    let content = read_abi_bytes(path)?;
+    serde_json::from_slice(&content).wrap_err_with(|| format!("unable to parse ABI file `{path}`"))
+}
+
+/// contents of ABI file at `path`, decompressed if it has `.zst` extension
+pub(crate) fn read_abi_bytes(path: &camino::Utf8Path) -> eyre::Result<Vec<u8>> {
+    let content =
+        std::fs::read(path).wrap_err_with(|| format!("unable to read ABI file `{path}`"))?;
+    if path.extension() == Some("zst") {
+        return zstd::decode_all(content.as_slice())
+            .wrap_err_with(|| format!("unable to decompress ABI file `{path}`"));
+    }
+    Ok(content)
+}
+
+/// Prints errors and warnings of [`validate`], returning an error if there are any errors
+pub fn check(contract_abi: &near_abi::AbiRoot, method: &str, json_args: &str) -> eyre::Result<()> {
+    let errors = validate(contract_abi, method, json_args)?;
+    for error in errors.iter() {
+        eprintln!("{error}");
+    }
+    let error_count = errors
+        .iter()
+        .filter(|error| error.severity == Severity::Error)
+        .count();
+    if error_count > 0 {
+        eprintln!();
+        return Err(eyre::eyre!(
+            "arguments of `{}` don't match contract's ABI: {} error(s)",
+            method,
+            error_count
+        ));
+    }
+    pretty_print::success(&format!(
+        "Arguments of `{}` match contract's ABI",
+        method.magenta()
+    ));
+    Ok(())
+}
+
+/// Mismatches of `json_args` against JSON schemas of `method`'s parameters in `contract_abi`
+///
+/// Arguments are expected to be a JSON object with a field per parameter. Fields, which
+/// the contract would ignore, are reported as warnings, as they're most likely misspelled parameters.
+/// Parameters, which accept `null`, may be omitted.
+///
+/// Returns an error if `method` isn't present in ABI, `json_args` isn't valid JSON,
+/// or `method` takes Borsh-serialized parameters, which can't be validated against a JSON value.
+pub fn validate(
+    contract_abi: &near_abi::AbiRoot,
+    method: &str,
+    json_args: &str,
+) -> eyre::Result<Vec<ValidationError>> {
+    let function = contract_abi
+        .body
+        .functions
+        .iter()
+        .find(|function| function.name == method)
+        .wrap_err_with(|| {
+            let available = contract_abi
+                .body
+                .functions
+                .iter()
+                .map(|function| format!("`{}`", function.name))
+                .collect::<Vec<_>>();
+            format!(
+                "function `{}` isn't present in contract's ABI, available functions: {}",
+                method,
+                available.join(", ")
+            )
+        })?;
+    let params = match &function.params {
+        AbiParameters::Json { args } => args,
+        AbiParameters::Borsh { .. } => {
+            return Err(eyre::eyre!(
+                "function `{}` takes Borsh-serialized arguments, only JSON arguments can be validated",
+                method
+            ));
+        }
+    };
+    let value: Value = serde_json::from_str(json_args)
+        .wrap_err_with(|| format!("arguments of `{method}` aren't valid JSON"))?;
+
+    let validator = Validator {
+        root: &contract_abi.body.root_schema,
+    };
+    let Value::Object(fields) = &value else {
+        return Ok(vec![ValidationError {
+            severity: Severity::Error,
+            path: ROOT_PATH.to_string(),
+            message: format!(
+                "expected object with arguments as fields, found {}",
+                type_name(&value)
+            ),
+        }]);
+    };
+    let mut errors = vec![];
+    for param in params.iter() {
+        let path = format!("{ROOT_PATH}.{}", param.name);
+        match fields.get(&param.name) {
+            Some(arg) => errors.extend(validator.validate(&param.type_schema, arg, &path)),
+            None if validator.accepts(&param.type_schema, &Value::Null) => {}
+            None => errors.push(ValidationError {
+                severity: Severity::Error,
+                path,
+                message: "missing required argument".to_string(),
+            }),
+        }
+    }
+    for name in fields.keys() {
+        if !params.iter().any(|param| &param.name == name) {
+            errors.push(ValidationError {
+                severity: Severity::Warning,
+                path: format!("{ROOT_PATH}.{name}"),
+                message: "unknown argument, it would be ignored by the contract".to_string(),
+            });
+        }
+    }
+    Ok(errors)
+}
+
+const ROOT_PATH: &str = "$";
+
+/// validator of JSON values against the subset of draft-07 JSON schema, emitted by `schemars`
+struct Validator<'a> {
+    /// schema, containing `definitions`, which `$ref`-s point to
+    root: &'a RootSchema,
+}
+
+impl Validator<'_> {
+    fn accepts(&self, schema: &Schema, value: &Value) -> bool {
+        self.validate(schema, value, ROOT_PATH).is_empty()
+    }
+
+    fn validate(&self, schema: &Schema, value: &Value, path: &str) -> Vec<ValidationError> {
+        let error = |message: String| ValidationError {
+            severity: Severity::Error,
+            path: path.to_string(),
+            message,
+        };
+        let object = match schema {
+            Schema::Bool(true) => return vec![],
+            Schema::Bool(false) => return vec![error("no value is allowed here".to_string())],
+            Schema::Object(object) => object,
+        };
+        if let Some(reference) = &object.reference {
+            return match self.resolve(reference) {
+                Some(definition) => self.validate(definition, value, path),
+                None => vec![error(format!("schema references unknown `{reference}`"))],
+            };
+        }
+
+        if let Some(instance_type) = &object.instance_type {
+            let types = instance_types(instance_type);
+            if !types.iter().any(|ty| type_matches(ty, value)) {
+                let expected = types.iter().map(|ty| type_label(ty)).collect::<Vec<_>>();
+                return vec![error(format!(
+                    "expected {}, found {}",
+                    expected.join(" or "),
+                    describe(value)
+                ))];
+            }
+        }
+        if let Some(enum_values) = &object.enum_values {
+            if !enum_values.contains(value) {
+                let expected = enum_values.iter().map(Value::to_string).collect::<Vec<_>>();
+                return vec![error(format!(
+                    "expected one of {}, found {}",
+                    expected.join(", "),
+                    value
+                ))];
+            }
+        }
+        if let Some(const_value) = &object.const_value {
+            if const_value != value {
+                return vec![error(format!("expected {const_value}, found {value}"))];
+            }
+        }
+
+        let mut errors = vec![];
+        match value {
+            Value::Number(number) => errors.extend(self.validate_number(object, number, path)),
+            Value::String(string) => {
+                if let Some(string_validation) = &object.string {
+                    errors.extend(validate_string(string_validation, string, path));
+                }
+            }
+            Value::Array(items) => {
+                if let Some(array) = &object.array {
+                    errors.extend(self.validate_array(array, items, path));
+                }
+            }
+            Value::Object(fields) => {
+                if let Some(object_validation) = &object.object {
+                    errors.extend(self.validate_object(object_validation, fields, path));
+                }
+            }
+            Value::Null | Value::Bool(_) => {}
+        }
+        if let Some(subschemas) = &object.subschemas {
+            for subschema in subschemas.all_of.iter().flatten() {
+                errors.extend(self.validate(subschema, value, path));
+            }
+            // `oneOf` is checked as `anyOf`: `serde` deserializes a value, matching several
+            // variants of an enum, into the first one, so there's nothing to report
+            for variants in [&subschemas.any_of, &subschemas.one_of]
+                .into_iter()
+                .flatten()
+            {
+                errors.extend(self.validate_variants(variants, value, path));
+            }
+            if let Some(not) = &subschemas.not {
+                if self.accepts(not, value) {
+                    errors.push(error(
+                        "value matches a schema, which it must not".to_string(),
+                    ));
+                }
+            }
+        }
+        errors
+    }
+
+    fn resolve(&self, reference: &str) -> Option<&Schema> {
+        definition_name(reference).and_then(|name| self.root.definitions.get(name))
+    }
+
+    fn validate_number(
+        &self,
+        object: &SchemaObject,
+        number: &serde_json::Number,
+        path: &str,
+    ) -> Vec<ValidationError> {
+        let error = |message: String| {
+            vec![ValidationError {
+                severity: Severity::Error,
+                path: path.to_string(),
+                message,
+            }]
+        };
+        if let Some((format, range)) = object
+            .format
+            .as_deref()
+            .and_then(|format| integer_range(format).map(|range| (format, range)))
+        {
+            if !range.contains(number) {
+                return error(format!("{number} is out of range of `{format}`"));
+            }
+        }
+        let Some(number_validation) = &object.number else {
+            return vec![];
+        };
+        let value = number.as_f64().unwrap_or(f64::NAN);
+        if let Some(minimum) = number_validation.minimum {
+            if value < minimum {
+                return error(format!("{number} is less than minimum {minimum}"));
+            }
+        }
+        if let Some(maximum) = number_validation.maximum {
+            if value > maximum {
+                return error(format!("{number} is greater than maximum {maximum}"));
+            }
+        }
+        if let Some(minimum) = number_validation.exclusive_minimum {
+            if value <= minimum {
+                return error(format!("{number} isn't greater than {minimum}"));
+            }
+        }
+        if let Some(maximum) = number_validation.exclusive_maximum {
+            if value >= maximum {
+                return error(format!("{number} isn't less than {maximum}"));
+            }
+        }
+        if let Some(multiple_of) = number_validation.multiple_of {
+            if (value / multiple_of).fract() != 0.0 {
+                return error(format!("{number} isn't a multiple of {multiple_of}"));
+            }
+        }
+        vec![]
+    }
+
+    fn validate_array(
+        &self,
+        array: &ArrayValidation,
+        items: &[Value],
+        path: &str,
+    ) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        let mut error = |message: String| {
+            errors.push(ValidationError {
+                severity: Severity::Error,
+                path: path.to_string(),
+                message,
+            })
+        };
+        if let Some(min_items) = array.min_items {
+            if items.len() < min_items as usize {
+                error(format!(
+                    "expected at least {min_items} item(s), found {}",
+                    items.len()
+                ));
+            }
+        }
+        if let Some(max_items) = array.max_items {
+            if items.len() > max_items as usize {
+                error(format!(
+                    "expected at most {max_items} item(s), found {}",
+                    items.len()
+                ));
+            }
+        }
+        if array.unique_items == Some(true) {
+            let duplicate = items
+                .iter()
+                .enumerate()
+                .find_map(|(index, item)| items[..index].contains(item).then_some(index));
+            if let Some(index) = duplicate {
+                error(format!("item at index {index} is a duplicate"));
+            }
+        }
+
+        for (index, item) in items.iter().enumerate() {
+            let schema = match &array.items {
+                Some(SingleOrVec::Single(schema)) => Some(schema.as_ref()),
+                Some(SingleOrVec::Vec(schemas)) => {
+                    schemas.get(index).or(array.additional_items.as_deref())
+                }
+                None => None,
+            };
+            if let Some(schema) = schema {
+                errors.extend(self.validate(schema, item, &format!("{path}[{index}]")));
+            }
+        }
+        errors
+    }
+
+    fn validate_object(
+        &self,
+        object: &ObjectValidation,
+        fields: &serde_json::Map<String, Value>,
+        path: &str,
+    ) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        for name in object.required.iter() {
+            if !fields.contains_key(name) {
+                errors.push(ValidationError {
+                    severity: Severity::Error,
+                    path: format!("{path}.{name}"),
+                    message: "missing required field".to_string(),
+                });
+            }
+        }
+        for (name, field) in fields.iter() {
+            let field_path = format!("{path}.{name}");
+            if let Some(schema) = object.properties.get(name) {
+                errors.extend(self.validate(schema, field, &field_path));
+                continue;
+            }
+            let pattern_schemas = object
+                .pattern_properties
+                .iter()
+                .filter(|(pattern, _)| {
+                    regex::Regex::new(pattern).is_ok_and(|regex| regex.is_match(name))
+                })
+                .map(|(_, schema)| schema)
+                .collect::<Vec<_>>();
+            if !pattern_schemas.is_empty() {
+                for schema in pattern_schemas {
+                    errors.extend(self.validate(schema, field, &field_path));
+                }
+                continue;
+            }
+            match object.additional_properties.as_deref() {
+                Some(Schema::Bool(false)) => errors.push(ValidationError {
+                    severity: Severity::Error,
+                    path: field_path,
+                    message: "unknown field".to_string(),
+                }),
+                Some(schema) => errors.extend(self.validate(schema, field, &field_path)),
+                None => {}
+            }
+        }
+        errors
+    }
+
+    /// `anyOf` validation; if the value fails all variants, errors of the single
+    /// variant, which it resembles, are reported, e.g. `T` of `Option<T>` for a non-null value
+    fn validate_variants(
+        &self,
+        variants: &[Schema],
+        value: &Value,
+        path: &str,
+    ) -> Vec<ValidationError> {
+        let mut resembling = vec![];
+        for variant in variants {
+            let errors = self.validate(variant, value, path);
+            if errors.is_empty() {
+                return vec![];
+            }
+            if self.resembles(variant, value) {
+                resembling.push(errors);
+            }
+        }
+        match <[_; 1]>::try_from(resembling) {
+            Ok([errors]) => errors,
+            Err(_) => vec![ValidationError {
+                severity: Severity::Error,
+                path: path.to_string(),
+                message: format!(
+                    "{} doesn't match any of {} allowed variants",
+                    describe(value),
+                    variants.len()
+                ),
+            }],
+        }
+    }
+
+    /// whether `value` passes shallow checks of `schema`: its type, enumerated values
+    /// and presence of required fields
+    fn resembles(&self, schema: &Schema, value: &Value) -> bool {
+        let Schema::Object(object) = schema else {
+            return matches!(schema, Schema::Bool(true));
+        };
+        if let Some(definition) = object.reference.as_deref().and_then(|r| self.resolve(r)) {
+            return self.resembles(definition, value);
+        }
+        let type_matches = object.instance_type.as_ref().is_none_or(|instance_type| {
+            instance_types(instance_type)
+                .iter()
+                .any(|ty| type_matches(ty, value))
+        });
+        let enum_matches = object
+            .enum_values
+            .as_ref()
+            .is_none_or(|enum_values| enum_values.contains(value));
+        let const_matches = object
+            .const_value
+            .as_ref()
+            .is_none_or(|const_value| const_value == value);
+        let required_present = match (&object.object, value) {
+            (Some(object_validation), Value::Object(fields)) => object_validation
+                .required
+                .iter()
+                .all(|name| fields.contains_key(name)),
+            _ => true,
+        };
+        type_matches && enum_matches && const_matches && required_present
+    }
+}
+
+fn validate_string(
+    string_validation: &StringValidation,
+    string: &str,
+    path: &str,
+) -> Vec<ValidationError> {
+    let length = string.chars().count();
+    let message = if string_validation
+        .min_length
+        .is_some_and(|min_length| length < min_length as usize)
+    {
+        format!(
+            "expected at least {} character(s), found {length}",
+            string_validation.min_length.unwrap_or_default()
+        )
+    } else if string_validation
+        .max_length
+        .is_some_and(|max_length| length > max_length as usize)
+    {
+        format!(
+            "expected at most {} character(s), found {length}",
+            string_validation.max_length.unwrap_or_default()
+        )
+    } else if let Some(pattern) = string_validation
+        .pattern
+        .as_deref()
+        .filter(|pattern| regex::Regex::new(pattern).is_ok_and(|regex| !regex.is_match(string)))
+    {
+        format!("\"{string}\" doesn't match pattern `{pattern}`")
+    } else {
+        return vec![];
+    };
+    vec![ValidationError {
+        severity: Severity::Error,
+        path: path.to_string(),
+        message,
+    }]
+}
+
+fn instance_types(instance_type: &SingleOrVec<InstanceType>) -> &[InstanceType] {
+    match instance_type {
+        SingleOrVec::Single(ty) => std::slice::from_ref(ty.as_ref()),
+        SingleOrVec::Vec(types) => types,
+    }
+}
+
+fn type_matches(ty: &InstanceType, value: &Value) -> bool {
+    match (ty, value) {
+        (InstanceType::Null, Value::Null)
+        | (InstanceType::Boolean, Value::Bool(_))
+        | (InstanceType::Object, Value::Object(_))
+        | (InstanceType::Array, Value::Array(_))
+        | (InstanceType::Number, Value::Number(_))
+        | (InstanceType::String, Value::String(_)) => true,
+        (InstanceType::Integer, Value::Number(number)) => {
+            number.is_i64()
+                || number.is_u64()
+                || number.as_f64().is_some_and(|value| value.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+fn type_label(ty: &InstanceType) -> &'static str {
+    match ty {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "boolean",
+        InstanceType::Object => "object",
+        InstanceType::Array => "array",
+        InstanceType::Number => "number",
+        InstanceType::String => "string",
+        InstanceType::Integer => "integer",
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// type of the value, followed by the value itself for scalars
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null | Value::Array(_) | Value::Object(_) => type_name(value).to_string(),
+        _ => format!("{} {}", type_name(value), value),
+    }
+}
+
+/// bounds of integer `format`-s, emitted by `schemars` for rust integer types
+fn integer_range(format: &str) -> Option<IntegerRange> {
+    let range = match format {
+        "uint8" => IntegerRange::Unsigned(u8::MAX.into()),
+        "uint16" => IntegerRange::Unsigned(u16::MAX.into()),
+        "uint32" => IntegerRange::Unsigned(u32::MAX.into()),
+        "uint64" | "uint" => IntegerRange::Unsigned(u64::MAX.into()),
+        "uint128" => IntegerRange::Unsigned(u128::MAX),
+        "int8" => IntegerRange::Signed(i8::MIN.into(), i8::MAX.into()),
+        "int16" => IntegerRange::Signed(i16::MIN.into(), i16::MAX.into()),
+        "int32" => IntegerRange::Signed(i32::MIN.into(), i32::MAX.into()),
+        "int64" | "int" => IntegerRange::Signed(i64::MIN.into(), i64::MAX.into()),
+        "int128" => IntegerRange::Signed(i128::MIN, i128::MAX),
+        _ => return None,
+    };
+    Some(range)
+}
+
+/// `0..=max` of unsigned formats, `min..=max` of signed ones;
+/// separate, as neither `i128` nor `u128` fits bounds of both `int128` and `uint128`
+#[derive(Clone, Copy)]
+enum IntegerRange {
+    Unsigned(u128),
+    Signed(i128, i128),
+}
+
+impl IntegerRange {
+    fn contains(self, number: &serde_json::Number) -> bool {
+        if let Some(value) = number.as_u64() {
+            return match self {
+                Self::Unsigned(max) => u128::from(value) <= max,
+                Self::Signed(min, max) => (min..=max).contains(&value.into()),
+            };
+        }
+        if let Some(value) = number.as_i64() {
+            return match self {
+                Self::Unsigned(_) => false,
+                Self::Signed(min, max) => (min..=max).contains(&value.into()),
+            };
+        }
+        // integers beyond 64 bits are parsed by `serde_json` as floats
+        let (min, max) = match self {
+            Self::Unsigned(max) => (0.0, max as f64),
+            Self::Signed(min, max) => (min as f64, max as f64),
+        };
+        number
+            .as_f64()
+            .is_some_and(|value| value >= min && value <= max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_root(params: serde_json::Value, definitions: serde_json::Value) -> near_abi::AbiRoot {
+        serde_json::from_value(serde_json::json!({
+            "schema_version": near_abi::SCHEMA_VERSION,
+            "metadata": {},
+            "body": {
+                "functions": [{
+                    "name": "new",
+                    "kind": "call",
+                    "modifiers": ["init"],
+                    "params": params,
+                }],
+                "root_schema": {
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "String",
+                    "type": "string",
+                    "definitions": definitions,
+                }
+            },
+        }))
+        .unwrap()
+    }
+
+    fn messages(errors: Vec<ValidationError>) -> Vec<String> {
+        errors
+            .into_iter()
+            .map(|error| format!("{} {}", error.path, error.message))
+            .collect()
+    }
+
+    fn init_abi() -> near_abi::AbiRoot {
+        abi_root(
+            serde_json::json!({
+                "serialization_type": "json",
+                "args": [
+                    { "name": "owner_id", "type_schema": { "$ref": "#/definitions/AccountId" } },
+                    { "name": "config", "type_schema": { "$ref": "#/definitions/Config" } },
+                    { "name": "memo", "type_schema": { "type": ["string", "null"] } }
+                ]
+            }),
+            serde_json::json!({
+                "AccountId": { "type": "string" },
+                "Config": {
+                    "type": "object",
+                    "required": ["fee", "mode"],
+                    "properties": {
+                        "fee": { "type": "integer", "format": "uint8", "minimum": 0.0 },
+                        "tokens": { "type": "array", "items": { "type": "string" }, "maxItems": 2 },
+                        "mode": { "$ref": "#/definitions/Mode" },
+                        "limit": { "anyOf": [{ "$ref": "#/definitions/Limit" }, { "type": "null" }] }
+                    },
+                    "additionalProperties": false
+                },
+                "Mode": {
+                    "oneOf": [
+                        { "type": "string", "enum": ["Open"] },
+                        {
+                            "type": "object",
+                            "required": ["Restricted"],
+                            "properties": { "Restricted": { "type": "array", "items": { "type": "string" } } },
+                            "additionalProperties": false
+                        }
+                    ]
+                },
+                "Limit": {
+                    "type": "object",
+                    "required": ["max"],
+                    "properties": { "max": { "type": "integer", "format": "uint32", "minimum": 0.0 } }
+                }
+            }),
+        )
+    }
+
+    #[test]
+    fn test_valid_args() {
+        let abi = init_abi();
+        for args in [
+            r#"{ "owner_id": "alice.near", "config": { "fee": 3, "mode": "Open" } }"#,
+            r#"{ "owner_id": "alice.near", "memo": null, "config": { "fee": 255, "mode": { "Restricted": ["bob.near"] }, "limit": { "max": 10 } } }"#,
+        ] {
+            assert_eq!(validate(&abi, "new", args).unwrap(), vec![], "{args}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        let abi = init_abi();
+        let args = r#"{
+            "ownerid": "alice.near",
+            "config": {
+                "fee": 256,
+                "tokens": ["a", 1, "c"],
+                "mode": { "Restricted": "bob.near" },
+                "limit": { "max": -1 },
+                "extra": true
+            }
+        }"#;
+        assert_eq!(
+            messages(validate(&abi, "new", args).unwrap()),
+            vec![
+                "$.owner_id missing required argument",
+                "$.config.extra unknown field",
+                "$.config.fee 256 is out of range of `uint8`",
+                "$.config.limit.max -1 is out of range of `uint32`",
+                "$.config.mode.Restricted expected array, found string \"bob.near\"",
+                "$.config.tokens expected at most 2 item(s), found 3",
+                "$.config.tokens[1] expected string, found number 1",
+                "$.ownerid unknown argument, it would be ignored by the contract",
+            ]
+        );
+
+        assert_eq!(
+            messages(validate(&abi, "new", "[]").unwrap()),
+            vec!["$ expected object with arguments as fields, found array"]
+        );
+        assert!(validate(&abi, "init", "{}").is_err());
+        assert!(validate(&abi, "new", "{ owner_id: 1 }").is_err());
+
+        let borsh_abi = abi_root(
+            serde_json::json!({
+                "serialization_type": "borsh",
+                "args": [{ "name": "value", "type_schema": { "declaration": "u8", "definitions": {} } }]
+            }),
+            serde_json::json!({}),
+        );
+        assert!(validate(&borsh_abi, "new", "{}").is_err());
+    }
+
+    #[test]
+    fn test_unknown_args_are_warnings() {
+        let abi = init_abi();
+        let args = r#"{ "owner_id": "alice.near", "config": { "fee": 3, "mode": "Open" }, "ownerid": "bob.near" }"#;
+        let errors = validate(&abi, "new", args).unwrap();
+        assert_eq!(
+            errors,
+            vec![ValidationError {
+                severity: Severity::Warning,
+                path: "$.ownerid".to_string(),
+                message: "unknown argument, it would be ignored by the contract".to_string(),
+            }]
+        );
+        check(&abi, "new", args).unwrap();
+        assert!(check(&abi, "new", r#"{ "owner_id": "alice.near" }"#).is_err());
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        let abi = abi_root(
+            serde_json::json!({
+                "serialization_type": "json",
+                "args": [
+                    { "name": "unsigned", "type_schema": { "type": "integer", "format": "uint128", "minimum": 0.0 } },
+                    { "name": "signed", "type_schema": { "type": "integer", "format": "int128" } }
+                ]
+            }),
+            serde_json::json!({}),
+        );
+        let args = |unsigned: &str, signed: &str| {
+            format!(r#"{{ "unsigned": {unsigned}, "signed": {signed} }}"#)
+        };
+        for (unsigned, signed) in [
+            ("0", "-1"),
+            (&u128::MAX.to_string(), &i128::MIN.to_string()),
+            // beyond `i128::MAX`, within `u128::MAX`
+            (
+                "200000000000000000000000000000000000000",
+                &i128::MAX.to_string(),
+            ),
+        ] {
+            let args = args(unsigned, signed);
+            assert_eq!(validate(&abi, "new", &args).unwrap(), vec![], "{args}");
+        }
+        assert_eq!(
+            messages(validate(&abi, "new", &args("-1", "1e39")).unwrap()),
+            vec![
+                "$.unsigned -1 is out of range of `uint128`",
+                "$.signed 1e+39 is out of range of `int128`",
+            ]
+        );
+    }
+}
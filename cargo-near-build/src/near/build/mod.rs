@@ -18,6 +18,9 @@ use crate::{
 
 use super::abi;
 
+#[cfg(feature = "docker")]
+mod standards;
+
 /// Protocol version at which the nearcore VM accepts the bulk-memory +
 /// nontrapping-float-to-int wasm opcodes that rustc >= 1.87 emits.
 const BULK_MEMORY_PROTOCOL_VERSION: u32 = 84;
@@ -149,6 +152,8 @@ pub fn run(args: Opts) -> eyre::Result<CompilationArtifact> {
         );
     }
 
+    let declared_standards = crate::types::near::standards::Standard::parse_all(&crate_metadata)?;
+
     pretty_print::handle_step("Checking the host environment...", || {
         if !cargo_native::target::wasm32_exists(effective_toolchain.clone()) {
             eyre::bail!("rust target `{}` is not installed", COMPILATION_TARGET);
@@ -239,6 +244,7 @@ pub fn run(args: Opts) -> eyre::Result<CompilationArtifact> {
                 !args.no_doc,
                 true,
                 &abi_feature_args,
+                &abi_types::filter::Filter::new(&args.abi_include, &args.abi_exclude)?,
                 &abi_env,
                 color,
             )?
@@ -341,6 +347,10 @@ pub fn run(args: Opts) -> eyre::Result<CompilationArtifact> {
 
     wasm_artifact.builder_version_info = Some(builder_version_info);
 
+    if !declared_standards.is_empty() {
+        check_declared_standards(&declared_standards, &wasm_artifact.path)?;
+    }
+
     // todo! if we embedded, check that the binary exports the __contract_abi symbol
 
     pretty_print::success(&format!(
@@ -370,6 +380,31 @@ pub fn run(args: Opts) -> eyre::Result<CompilationArtifact> {
     Ok(wasm_artifact)
 }
 
+#[cfg(feature = "docker")]
+fn check_declared_standards(
+    declared: &[crate::types::near::standards::Standard],
+    wasm_path: &camino::Utf8Path,
+) -> eyre::Result<()> {
+    pretty_print::handle_step(
+        "Checking standards of `[package.metadata.near]` against NEP-330 metadata of the contract...",
+        || standards::check(declared, wasm_path),
+    )
+}
+
+/// extracting NEP-330 metadata from wasm requires `docker` feature, see [`crate::near::inspect`]
+#[cfg(not(feature = "docker"))]
+fn check_declared_standards(
+    _declared: &[crate::types::near::standards::Standard],
+    _wasm_path: &camino::Utf8Path,
+) -> eyre::Result<()> {
+    pretty_print::step(
+        &"WARN: Skipping check of standards of `[package.metadata.near]`, it requires `docker` feature...\n"
+            .yellow()
+            .to_string(),
+    );
+    Ok(())
+}
+
 fn is_newer_than(prev: &Utf8PathBuf, next: &Utf8PathBuf) -> bool {
     // (1) if `next` does not yet exist, `metadata_of_prev.modified()` will be greater than
     // `std::time::SystemTime::UNIX_EPOCH`;
@@ -0,0 +1,109 @@
+use camino::Utf8Path;
+use near_verify_rs::types::contract_source_metadata::Standard as EmbeddedStandard;
+
+use crate::types::near::standards::Standard;
+
+/// `near-sdk` adds it to standards of every contract, so it isn't required to be declared in Cargo.toml
+const NEP330: &str = "nep330";
+
+/// checks that standards, declared in `[package.metadata.near]` of Cargo.toml, are the ones
+/// embedded into NEP-330 metadata of the wasm at `wasm_path` from `#[near(contract_metadata(standard(..)))]`
+pub(super) fn check(declared: &[Standard], wasm_path: &Utf8Path) -> eyre::Result<()> {
+    let metadata = crate::near::inspect::contract_source_metadata(wasm_path)?;
+    compare(declared, &metadata.standards)
+}
+
+fn compare(declared: &[Standard], embedded: &[EmbeddedStandard]) -> eyre::Result<()> {
+    let mut mismatches = vec![];
+    for standard in declared {
+        match embedded
+            .iter()
+            .find(|embedded| embedded.standard == standard.standard)
+        {
+            None => mismatches.push(format!(
+                "`{}` {} is declared in Cargo.toml, but isn't implemented by the contract",
+                standard.standard, standard.version
+            )),
+            Some(embedded) if embedded.version != standard.version => mismatches.push(format!(
+                "`{}` is declared in Cargo.toml with {} version, but is implemented by the contract with {} one",
+                standard.standard, standard.version, embedded.version
+            )),
+            Some(_) => {}
+        }
+    }
+    for embedded in embedded {
+        if embedded.standard != NEP330
+            && !declared
+                .iter()
+                .any(|standard| standard.standard == embedded.standard)
+        {
+            mismatches.push(format!(
+                "`{}` {} is implemented by the contract, but isn't declared in Cargo.toml",
+                embedded.standard, embedded.version
+            ));
+        }
+    }
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    Err(eyre::eyre!(
+        "`standards` of `[package.metadata.near]` in Cargo.toml don't match \
+         `#[near(contract_metadata(standard(..)))]` of the contract:\n{}",
+        mismatches.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared(standard: &str, version: &str) -> Standard {
+        Standard {
+            standard: standard.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    fn embedded(standard: &str, version: &str) -> EmbeddedStandard {
+        EmbeddedStandard {
+            standard: standard.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn declared_standards_are_compared_with_embedded_ones() {
+        let embedded = [
+            embedded("nep141", "1.0.0"),
+            embedded("nep145", "1.0.0"),
+            embedded("nep330", "1.2.0"),
+        ];
+        compare(
+            &[declared("nep141", "1.0.0"), declared("nep145", "1.0.0")],
+            &embedded,
+        )
+        .unwrap();
+
+        for (declared, expected) in [
+            (
+                vec![declared("nep141", "1.0.0")],
+                "`nep145` 1.0.0 is implemented by the contract, but isn't declared",
+            ),
+            (
+                vec![
+                    declared("nep141", "1.0.0"),
+                    declared("nep145", "1.0.0"),
+                    declared("nep171", "1.0.0"),
+                ],
+                "`nep171` 1.0.0 is declared in Cargo.toml, but isn't implemented",
+            ),
+            (
+                vec![declared("nep141", "1.1.0"), declared("nep145", "1.0.0")],
+                "`nep141` is declared in Cargo.toml with 1.1.0 version, but is implemented by the contract with 1.0.0 one",
+            ),
+        ] {
+            let err = compare(&declared, &embedded).err().unwrap();
+            assert!(err.to_string().contains(expected), "{declared:?}: {err}");
+        }
+    }
+}
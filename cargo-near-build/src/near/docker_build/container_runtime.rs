@@ -0,0 +1,402 @@
+//! [`ContainerRuntime::Docker`] builds are run by [`near_verify_rs`], the same way verifiers run them.
+//! It invokes `docker` by name, so for other runtimes its `docker_checks` and `nep330_build::run`
+//! are mirrored here with the runtime's binary, until `near-verify-rs` takes the binary as a parameter.
+
+use std::io::IsTerminal;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use eyre::ContextCompat;
+use near_verify_rs::types::contract_source_metadata::{BuildInfo, ContractSourceMetadata};
+
+use crate::env_keys::nep330::NEP330_REPO_MOUNT;
+use crate::pretty_print;
+use crate::types::cargo::manifest_path::{MANIFEST_FILE_NAME, ManifestPath};
+use crate::types::cargo::metadata::CrateMetadata;
+use crate::types::near::build::common_buildtime_env::CargoTargetDir;
+use crate::types::near::docker_build::container_runtime::ContainerRuntime;
+
+/// exit code of a command, which the user isn't permitted to execute
+const PERM_DENIED_STATUS: i32 = 126;
+
+/// checks that `runtime` is installed and is able to run containers for the current user
+pub(crate) fn sanity_check(runtime: ContainerRuntime, quiet: bool) -> eyre::Result<()> {
+    if runtime == ContainerRuntime::Docker {
+        return near_verify_rs::logic::docker_checks::sanity::check(quiet);
+    }
+    let mut command = Command::new(runtime.binary());
+    command.arg("info");
+    let err_report = format!("`{runtime}` sanity check failed!");
+    let output = {
+        let result = command.output();
+        handle_io_error(runtime, &command, result, &err_report, quiet)?
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !quiet {
+            println!();
+            println!("{}", stderr.yellow());
+            if output.status.code() == Some(PERM_DENIED_STATUS)
+                || stderr.to_lowercase().contains("permission denied")
+            {
+                println!("{}", "Permission denied!".cyan());
+            }
+        }
+        print_command_status(output.status, &command, quiet);
+        return Err(eyre::eyre!(err_report));
+    }
+    Ok(())
+}
+
+/// pulls `image` with `runtime`
+pub(crate) fn pull_image(runtime: ContainerRuntime, image: &str, quiet: bool) -> eyre::Result<()> {
+    if runtime == ContainerRuntime::Docker {
+        return near_verify_rs::logic::docker_checks::pull_image::check(image, quiet);
+    }
+    if !quiet {
+        println!("{} {}", "container image to be used:".green(), image);
+        println!();
+    }
+    let mut command = Command::new(runtime.binary());
+    command.args(["pull", image]);
+    if quiet {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    let err_report = format!("Image `{image}` could not be found in registry!");
+    let status = {
+        let result = command.status();
+        handle_io_error(runtime, &command, result, &err_report, quiet)?
+    };
+    if !status.success() {
+        print_command_status(status, &command, quiet);
+        return Err(eyre::eyre!(err_report));
+    }
+    Ok(())
+}
+
+/// runs NEP-330 build command of `contract_source_metadata` with `runtime` in a container,
+/// where `contract_source_workdir` is mounted at [`NEP330_REPO_MOUNT`], and returns path of the built wasm
+/// in `contract_source_workdir`
+pub(crate) fn run_build(
+    runtime: ContainerRuntime,
+    contract_source_metadata: &ContractSourceMetadata,
+    contract_source_workdir: camino::Utf8PathBuf,
+    additional_run_args: Vec<String>,
+    quiet: bool,
+) -> eyre::Result<camino::Utf8PathBuf> {
+    if runtime == ContainerRuntime::Docker {
+        return near_verify_rs::logic::nep330_build::run(
+            contract_source_metadata.clone(),
+            contract_source_workdir,
+            additional_run_args,
+            quiet,
+        );
+    }
+    let build_info = contract_source_metadata
+        .build_info
+        .as_ref()
+        .wrap_err("cannot be [Option::None] as per [ContractSourceMetadata::validate] check")?;
+    let shell_escaped_build_command =
+        near_verify_rs::logic::shell_escape_nep330_build_command(build_info.build_command.clone());
+    if !quiet {
+        println!(
+            "{} {}",
+            "build command in container:".green(),
+            shell_escaped_build_command
+        );
+        println!();
+    }
+    let run_args = RunArgs {
+        user: host_user(),
+        container_name: container_name(),
+        interactive: std::io::stdin().is_terminal()
+            && std::env::var(near_verify_rs::env_keys::nonspec::SERVER_DISABLE_INTERACTIVE)
+                .is_err(),
+        env_args: contract_source_metadata.docker_env_args(),
+        additional_run_args,
+    };
+    let mut command = run_command(runtime, build_info, &contract_source_workdir, run_args)?;
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "Container command:\n{}",
+        pretty_print::indent_payload(&format!("{command:#?}"))
+    );
+    if quiet {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+
+    let err_report = format!("Reproducible build in `{runtime}` container failed.");
+    let status = {
+        let result = command.status();
+        handle_io_error(runtime, &command, result, &err_report, quiet)?
+    };
+    if !status.success() {
+        print_command_status(status, &command, quiet);
+        return Err(eyre::eyre!(err_report));
+    }
+    wasm_output_path(build_info, contract_source_workdir)
+}
+
+/// arguments of `run` command, which depend on the host and the process
+struct RunArgs {
+    /// `<uid>:<gid>`
+    user: String,
+    container_name: String,
+    interactive: bool,
+    env_args: Vec<String>,
+    additional_run_args: Vec<String>,
+}
+
+fn run_command(
+    runtime: ContainerRuntime,
+    build_info: &BuildInfo,
+    contract_source_workdir: &camino::Utf8Path,
+    run_args: RunArgs,
+) -> eyre::Result<Command> {
+    // `:z` requests an SELinux shared relabel of the bind mount, needed on
+    // enforcing hosts (Fedora/RHEL) and ignored elsewhere; `near-verify-rs` mounts it the same way
+    let volume = format!("{contract_source_workdir}:{NEP330_REPO_MOUNT}:z");
+    let workdir = {
+        let mut path = unix_path::PathBuf::from(NEP330_REPO_MOUNT);
+        path.push(unix_path::Path::new(&build_info.contract_path));
+        path.to_str()
+            .wrap_err("non UTF-8 unix path computed as crate path")?
+            .to_string()
+    };
+
+    let mut command = Command::new(runtime.binary());
+    command.arg("run");
+    command.args(["-u", &run_args.user]);
+    command.args(["--name", &run_args.container_name]);
+    command.args(["--volume", &volume]);
+    command.arg("--rm");
+    command.args(["--workdir", &workdir]);
+    if run_args.interactive {
+        command.arg("-it");
+    }
+    command.args(run_args.env_args);
+    command.args(run_args.additional_run_args);
+    command.args([&build_info.build_environment, "/bin/bash", "-c"]);
+    command.arg(near_verify_rs::logic::shell_escape_nep330_build_command(
+        build_info.build_command.clone(),
+    ));
+    Ok(command)
+}
+
+/// on Linux the volume is mounted natively, so the unprivileged user inside the container should
+/// be the host user to write to the mounted folder, otherwise built files end up owned by another user
+fn host_user() -> String {
+    #[cfg(target_os = "linux")]
+    {
+        format!("{}:{}", nix::unistd::getuid(), nix::unistd::getgid())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        "1000:1000".to_string()
+    }
+}
+
+fn container_name() -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    format!("cargo-near-{}-{}", timestamp, std::process::id())
+}
+
+/// NEP-330 1.3.0 `output_wasm_path` in the container, if it's set, otherwise the legacy output path of
+/// a rust crate at `contract_path`, see [`CrateMetadata::get_legacy_cargo_near_output_path`]
+fn wasm_output_path(
+    build_info: &BuildInfo,
+    contract_source_workdir: camino::Utf8PathBuf,
+) -> eyre::Result<camino::Utf8PathBuf> {
+    let path = match build_info.output_wasm_path {
+        Some(ref output_wasm_path) => {
+            let relative_path = pathdiff::diff_utf8_paths(
+                camino::Utf8Path::new(output_wasm_path),
+                camino::Utf8Path::new(NEP330_REPO_MOUNT),
+            )
+            .filter(|path| !path.starts_with(".."))
+            .wrap_err_with(|| {
+                format!("output wasm path `{output_wasm_path}` is outside of `{NEP330_REPO_MOUNT}`")
+            })?;
+            contract_source_workdir.join(relative_path)
+        }
+        None => {
+            let mut manifest_path = contract_source_workdir;
+            manifest_path.extend(
+                unix_path::Path::new(&build_info.contract_path)
+                    .iter()
+                    .map(|component| component.to_string_lossy().into_owned()),
+            );
+            manifest_path.push(MANIFEST_FILE_NAME);
+            let crate_metadata = CrateMetadata::collect(
+                ManifestPath::try_from(manifest_path)?,
+                false,
+                &CargoTargetDir::NoOp,
+                None,
+            )?;
+            crate_metadata
+                .get_legacy_cargo_near_output_path(None)?
+                .get_wasm_file()
+                .clone()
+        }
+    };
+    if !path.is_file() {
+        return Err(eyre::eyre!(
+            "assumed artifact result path of a container build not found: `{path}`"
+        ));
+    }
+    Ok(path)
+}
+
+fn handle_io_error<T>(
+    runtime: ContainerRuntime,
+    command: &Command,
+    result: std::io::Result<T>,
+    err_report: &str,
+    quiet: bool,
+) -> eyre::Result<T> {
+    result.map_err(|err| {
+        if !quiet {
+            println!();
+            if err.kind() == std::io::ErrorKind::NotFound {
+                println!(
+                    "{}",
+                    format!("`{runtime}` executable isn't available").yellow()
+                );
+            } else {
+                println!(
+                    "{}",
+                    format!("Error obtaining status from executing command `{command:?}`: {err}")
+                        .yellow()
+                );
+            }
+        }
+        eyre::eyre!(err_report.to_string())
+    })
+}
+
+fn print_command_status(status: ExitStatus, command: &Command, quiet: bool) {
+    if quiet {
+        return;
+    }
+    let command = std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    println!();
+    println!(
+        "{}",
+        format!("See output above ↑↑↑.\nCommand `{command}` failed with: {status}.").yellow()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_info(output_wasm_path: Option<&str>) -> BuildInfo {
+        BuildInfo {
+            build_environment: "sourcescan/cargo-near:0.13.0-rust-1.84.0@sha256:abc".to_string(),
+            build_command: vec![
+                "cargo".to_string(),
+                "near".to_string(),
+                "build".to_string(),
+                "non-reproducible-wasm".to_string(),
+                "--locked".to_string(),
+            ],
+            contract_path: "contracts/token".to_string(),
+            source_code_snapshot: "git+https://github.com/org/contract?rev=abc".to_string(),
+            output_wasm_path: output_wasm_path.map(ToString::to_string),
+        }
+    }
+
+    fn run_args(interactive: bool) -> RunArgs {
+        RunArgs {
+            user: "1000:1000".to_string(),
+            container_name: "cargo-near-1-2".to_string(),
+            interactive,
+            env_args: vec!["--env".to_string(), "NEP330_LINK=link".to_string()],
+            additional_run_args: ContainerRuntime::Podman.additional_run_args(),
+        }
+    }
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn run_command_is_executed_with_runtime_binary() {
+        let workdir = camino::Utf8Path::new("/tmp/build-site");
+        for runtime in [ContainerRuntime::Podman, ContainerRuntime::Nerdctl] {
+            let command =
+                run_command(runtime, &build_info(None), workdir, run_args(false)).unwrap();
+            assert_eq!(command.get_program(), runtime.binary());
+        }
+
+        let command = run_command(
+            ContainerRuntime::Podman,
+            &build_info(None),
+            workdir,
+            run_args(true),
+        )
+        .unwrap();
+        assert_eq!(
+            args(&command),
+            [
+                "run",
+                "-u",
+                "1000:1000",
+                "--name",
+                "cargo-near-1-2",
+                "--volume",
+                "/tmp/build-site:/home/near/code:z",
+                "--rm",
+                "--workdir",
+                "/home/near/code/contracts/token",
+                "-it",
+                "--env",
+                "NEP330_LINK=link",
+                "--userns=keep-id",
+                "sourcescan/cargo-near:0.13.0-rust-1.84.0@sha256:abc",
+                "/bin/bash",
+                "-c",
+                "cargo near build non-reproducible-wasm --locked",
+            ]
+        );
+    }
+
+    #[test]
+    fn explicit_output_wasm_path_is_mapped_to_build_site() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let workdir = camino::Utf8PathBuf::try_from(tmp_dir.path().to_path_buf()).unwrap();
+        let wasm_dir = workdir.join("target/near");
+        std::fs::create_dir_all(&wasm_dir).unwrap();
+        std::fs::write(wasm_dir.join("contract.wasm"), b"\0asm").unwrap();
+
+        let path = wasm_output_path(
+            &build_info(Some("/home/near/code/target/near/contract.wasm")),
+            workdir.clone(),
+        )
+        .unwrap();
+        assert_eq!(path, wasm_dir.join("contract.wasm"));
+
+        for output_wasm_path in [
+            "/home/near/code/target/near/missing.wasm",
+            "/home/near/other/contract.wasm",
+        ] {
+            assert!(
+                wasm_output_path(&build_info(Some(output_wasm_path)), workdir.clone()).is_err(),
+                "{output_wasm_path}"
+            );
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+
+use crate::camino;
+use crate::types::cargo::manifest_path::MANIFEST_FILE_NAME;
+use crate::types::cargo::metadata::CrateMetadata;
+
+/// checks that path dependencies of the contract (dev ones included, as `cargo` resolves them too)
+/// and path `[patch]` entries of its workspace will be present on the temporary build site,
+/// which is a clone of the repository at `commit`; warns about git dependencies without a pinned `rev`
+pub fn check(
+    crate_metadata: &CrateMetadata,
+    repo_root: &camino::Utf8Path,
+    commit: git2::Oid,
+) -> eyre::Result<()> {
+    let unpinned = crate_metadata
+        .packages_in_graph(true)
+        .filter_map(|pkg| {
+            let source = pkg.source.as_ref()?;
+            (is_pinned_git_source(&source.repr) == Some(false))
+                .then(|| format!("  - `{}` from {}", pkg.name, source.repr))
+        })
+        .collect::<Vec<_>>();
+    if !unpinned.is_empty() {
+        println!(
+            "{}: {}\n{}",
+            "WARNING".red(),
+            "git dependencies without a pinned `rev`, their commits are fixed only by Cargo.lock:"
+                .yellow(),
+            unpinned.join("\n")
+        );
+    }
+
+    let repo_root = super::canonicalize(repo_root)?;
+    let mut unavailable = vec![];
+    for (path, described) in path_dependencies(crate_metadata)? {
+        let location = locate(&path, &repo_root, commit)?;
+        if location != Location::Committed {
+            unavailable.push(format!("  - {described} at `{path}`: {location}"));
+        }
+    }
+    if unavailable.is_empty() {
+        return Ok(());
+    }
+    Err(eyre::eyre!(
+        "{} path dependencies won't be present on the temporary build site, \
+        which is a clone of `{}` at {}:\n\n{}\n\n\
+        Either commit them into the repository, or depend on them by a git url with a pinned `rev`, \
+        or by a version from a registry",
+        unavailable.len(),
+        repo_root,
+        commit,
+        unavailable.join("\n")
+    ))
+}
+
+/// directories of path-sourced packages and of path `[patch]` entries of the workspace manifest,
+/// with a description of each of them
+fn path_dependencies(
+    crate_metadata: &CrateMetadata,
+) -> eyre::Result<BTreeMap<camino::Utf8PathBuf, String>> {
+    let mut result = BTreeMap::new();
+    for pkg in crate_metadata
+        .packages_in_graph(true)
+        .filter(|pkg| pkg.source.is_none())
+    {
+        let dir = pkg
+            .manifest_path
+            .parent()
+            .wrap_err_with(|| format!("manifest of `{}` has no parent directory", pkg.name))?;
+        result.insert(dir.to_path_buf(), format!("package `{}`", pkg.name));
+    }
+
+    let workspace_root = &crate_metadata.raw_metadata.workspace_root;
+    let workspace_manifest = workspace_root.join(MANIFEST_FILE_NAME);
+    let contents = std::fs::read_to_string(&workspace_manifest)
+        .wrap_err_with(|| format!("failed to read `{workspace_manifest}`"))?;
+    for (source, name, path) in patch_paths(&contents)
+        .wrap_err_with(|| format!("failed to parse `[patch]` of `{workspace_manifest}`"))?
+    {
+        result
+            .entry(workspace_root.join(path))
+            .or_insert_with(|| format!("`[patch.{source}]` entry `{name}`"));
+    }
+    Ok(result)
+}
+
+/// `(source, name, path)` of `[patch.<source>] <name> = { path = "<path>" }` entries of a manifest
+fn patch_paths(manifest: &str) -> eyre::Result<Vec<(String, String, String)>> {
+    let document = manifest.parse::<toml_edit::DocumentMut>()?;
+    let Some(patch) = document.get("patch").and_then(|item| item.as_table_like()) else {
+        return Ok(vec![]);
+    };
+    let mut result = vec![];
+    for (source, entries) in patch.iter() {
+        let Some(entries) = entries.as_table_like() else {
+            continue;
+        };
+        for (name, entry) in entries.iter() {
+            if let Some(path) = entry
+                .as_table_like()
+                .and_then(|entry| entry.get("path"))
+                .and_then(|path| path.as_str())
+            {
+                result.push((source.to_string(), name.to_string(), path.to_string()));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// `None` for sources other than git ones,
+/// e.g. `git+https://github.com/org/repo?rev=0a1b2c3#0a1b2c3...` is pinned,
+/// `git+https://github.com/org/repo?branch=main#0a1b2c3...` isn't
+fn is_pinned_git_source(repr: &str) -> Option<bool> {
+    let url = url::Url::parse(repr.strip_prefix("git+")?).ok()?;
+    Some(url.query_pairs().any(|(key, _)| key == "rev"))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Location {
+    Committed,
+    Missing,
+    OutsideOfRepository,
+    Ignored,
+    Uncommitted,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Committed => write!(f, "committed"),
+            Self::Missing => write!(f, "doesn't exist"),
+            Self::OutsideOfRepository => write!(f, "outside of the repository"),
+            Self::Ignored => write!(f, "ignored by git"),
+            Self::Uncommitted => write!(f, "not committed into git"),
+        }
+    }
+}
+
+/// location of the `Cargo.toml` of a package in `dir` relative to the repository at `commit`,
+/// submodules of which are considered to be its parts, as they're cloned too
+fn locate(
+    dir: &camino::Utf8Path,
+    repo_root: &camino::Utf8Path,
+    commit: git2::Oid,
+) -> eyre::Result<Location> {
+    let Ok(dir) = super::canonicalize(dir) else {
+        return Ok(Location::Missing);
+    };
+    if !dir.starts_with(repo_root) {
+        return Ok(Location::OutsideOfRepository);
+    }
+    let repo = git2::Repository::discover(&dir)?;
+    let workdir = super::canonicalize(
+        repo.workdir()
+            .wrap_err("no workdir discovered in bare repository")?,
+    )?;
+    let manifest = dir.join(MANIFEST_FILE_NAME);
+    let in_workdir = manifest
+        .strip_prefix(&workdir)
+        .wrap_err_with(|| format!("`{dir}` isn't in workdir `{workdir}`"))?;
+    if repo.is_path_ignored(in_workdir)? {
+        return Ok(Location::Ignored);
+    }
+    let in_repo = manifest
+        .strip_prefix(repo_root)
+        .wrap_err_with(|| format!("`{dir}` isn't in repository `{repo_root}`"))?;
+    let root_repo = git2::Repository::open(repo_root)?;
+    Ok(if is_committed(&root_repo, commit, in_repo)? {
+        Location::Committed
+    } else {
+        Location::Uncommitted
+    })
+}
+
+/// whether `path` is present in the tree of `commit` of `repo`;
+/// submodules are descended into at commits, recorded for them in the tree
+fn is_committed(
+    repo: &git2::Repository,
+    commit: git2::Oid,
+    path: &camino::Utf8Path,
+) -> eyre::Result<bool> {
+    let tree = repo.find_commit(commit)?.tree()?;
+    let mut prefix = camino::Utf8PathBuf::new();
+    let mut components = path.components();
+    while let Some(component) = components.next() {
+        prefix.push(component);
+        let Ok(entry) = tree.get_path(prefix.as_std_path()) else {
+            return Ok(false);
+        };
+        if entry.kind() == Some(git2::ObjectType::Commit) {
+            let workdir = repo
+                .workdir()
+                .wrap_err("no workdir discovered in bare repository")?;
+            let Ok(submodule) = git2::Repository::open(workdir.join(prefix.as_std_path())) else {
+                return Ok(false);
+            };
+            if submodule.find_commit(entry.id()).is_err() {
+                return Ok(false);
+            }
+            return is_committed(&submodule, entry.id(), components.as_path());
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_dependencies_are_located_relative_to_repository() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let tmp_root = super::super::canonicalize(tmp_dir.path()).unwrap();
+        let repo_root = tmp_root.join("repo");
+        let repo = git2::Repository::init(&repo_root).unwrap();
+        std::fs::write(repo_root.join(".gitignore"), "generated/\n").unwrap();
+        for dir in ["committed", "untracked", "generated", "../other-repo"] {
+            std::fs::create_dir_all(repo_root.join(dir)).unwrap();
+            std::fs::write(repo_root.join(dir).join(MANIFEST_FILE_NAME), "").unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index
+            .add_path(std::path::Path::new("committed/Cargo.toml"))
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let init = repo
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+
+        for (dir, expected) in [
+            ("committed", Location::Committed),
+            ("untracked", Location::Uncommitted),
+            ("generated", Location::Ignored),
+            ("../other-repo", Location::OutsideOfRepository),
+            ("missing", Location::Missing),
+        ] {
+            assert_eq!(
+                locate(&repo_root.join(dir), &repo_root, init).unwrap(),
+                expected,
+                "{dir}"
+            );
+        }
+
+        // committed after the built commit
+        index
+            .add_path(std::path::Path::new("untracked/Cargo.toml"))
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.find_commit(init).unwrap();
+        let later = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "later",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+        let untracked = repo_root.join("untracked");
+        assert_eq!(
+            locate(&untracked, &repo_root, init).unwrap(),
+            Location::Uncommitted
+        );
+        assert_eq!(
+            locate(&untracked, &repo_root, later).unwrap(),
+            Location::Committed
+        );
+    }
+
+    #[test]
+    fn patches_and_git_sources() {
+        let manifest = r#"
+[workspace]
+members = ["contract"]
+
+[patch.crates-io]
+near-sdk = { path = "../near-sdk-rs/near-sdk" }
+borsh = { git = "https://github.com/near/borsh-rs", rev = "0a1b2c3" }
+
+[patch."https://github.com/org/repo"]
+common = { path = "vendor/common" }
+"#;
+        assert_eq!(
+            patch_paths(manifest).unwrap(),
+            vec![
+                (
+                    "crates-io".to_string(),
+                    "near-sdk".to_string(),
+                    "../near-sdk-rs/near-sdk".to_string()
+                ),
+                (
+                    "https://github.com/org/repo".to_string(),
+                    "common".to_string(),
+                    "vendor/common".to_string()
+                ),
+            ]
+        );
+
+        assert_eq!(
+            is_pinned_git_source("git+https://github.com/org/repo?rev=0a1b2c3#0a1b2c3d4e"),
+            Some(true)
+        );
+        assert_eq!(
+            is_pinned_git_source("git+https://github.com/org/repo?branch=main#0a1b2c3d4e"),
+            Some(false)
+        );
+        assert_eq!(
+            is_pinned_git_source("git+https://github.com/org/repo#0a1b2c3d4e"),
+            Some(false)
+        );
+        assert_eq!(
+            is_pinned_git_source("registry+https://github.com/rust-lang/crates.io-index"),
+            None
+        );
+    }
+}
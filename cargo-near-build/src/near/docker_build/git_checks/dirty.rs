@@ -4,15 +4,14 @@ use colored::Colorize;
 use eyre::{ContextCompat, WrapErr};
 
 use crate::camino;
+use crate::types::cargo::metadata::CrateMetadata;
 use crate::types::near::build::input::BuildContext;
 use crate::types::near::docker_build::WARN_BECOMES_ERR;
-use serde_json::to_string;
+use crate::types::near::docker_build::crate_in_repo;
+use crate::types::near::docker_build::metadata::parse::ReproducibleBuild;
 
-pub fn check_then_handle(
-    context: BuildContext,
-    repo_root: &camino::Utf8PathBuf,
-) -> eyre::Result<()> {
-    let result = check(repo_root);
+pub fn check_then_handle(context: BuildContext, scope: &Scope) -> eyre::Result<()> {
+    let result = check(scope);
     match (result, context) {
         (Err(err), BuildContext::Deploy { .. }) => {
             println!(
@@ -36,25 +35,165 @@ pub fn check_then_handle(
         _ => Ok(()),
     }
 }
-fn check(repo_root: &camino::Utf8PathBuf) -> eyre::Result<()> {
-    let repo = git2::Repository::open(repo_root)?;
-    let mut dirty_files = Vec::new();
+
+/// determines, which modified or untracked files of the repository make it dirty
+pub struct Scope {
+    repo_root: camino::Utf8PathBuf,
+    /// globs of `dirty_allowlist` and of `--allow-dirty`
+    allowlist: globset::GlobSet,
+    /// directories of the contract's crate and of its path dependencies;
+    /// `None`, if cargo metadata couldn't be collected, then all files are considered to affect the build
+    package_dirs: Option<Vec<camino::Utf8PathBuf>>,
+    /// directories from the contract's crate up to the root of the repository, own files of which
+    /// (`Cargo.toml`, `Cargo.lock` of the workspace, `rust-toolchain.toml`, `.cargo/config.toml`) can affect the build
+    ancestor_dirs: Vec<camino::Utf8PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Classified {
+    Dirty,
+    Allowlisted,
+    /// outside of the contract's crate and of its path dependencies
+    Unrelated,
+}
+
+impl Scope {
+    /// `crate_metadata` is the one of the contract in the repository, not on the temporary build site
+    pub fn collect(
+        crate_in_repo: &crate_in_repo::Crate,
+        crate_metadata: Result<&CrateMetadata, &eyre::Report>,
+        allow_dirty: &[String],
+    ) -> eyre::Result<Self> {
+        let repo_root = super::canonicalize(&crate_in_repo.repo_root)?;
+        let crate_root = super::canonicalize(&crate_in_repo.crate_root)?;
+
+        let mut patterns = allow_dirty.to_vec();
+        let package_dirs = match crate_metadata {
+            Ok(crate_metadata) => {
+                patterns.extend(ReproducibleBuild::parse_dirty_allowlist(crate_metadata)?);
+                Some(
+                    crate_metadata
+                        .local_package_dirs()
+                        .iter()
+                        .map(super::canonicalize)
+                        .collect::<eyre::Result<Vec<_>>>()?,
+                )
+            }
+            Err(err) => {
+                println!(
+                    "{}: {:#}\n{}",
+                    "unable to collect cargo metadata of the contract".yellow(),
+                    err,
+                    "all files of the repository are checked".yellow()
+                );
+                None
+            }
+        };
+        let ancestor_dirs = crate_root
+            .ancestors()
+            .take_while(|dir| dir.starts_with(&repo_root))
+            .map(ToOwned::to_owned)
+            .collect();
+        Ok(Self {
+            repo_root,
+            allowlist: allowlist(&patterns)?,
+            package_dirs,
+            ancestor_dirs,
+        })
+    }
+
+    fn classify(&self, file: &camino::Utf8Path) -> Classified {
+        let Ok(relative) = file.strip_prefix(&self.repo_root) else {
+            return Classified::Dirty;
+        };
+        if !self.affects_build(file) {
+            return Classified::Unrelated;
+        }
+        if self.allowlist.is_match(relative) {
+            return Classified::Allowlisted;
+        }
+        Classified::Dirty
+    }
+
+    fn affects_build(&self, file: &camino::Utf8Path) -> bool {
+        let Some(package_dirs) = &self.package_dirs else {
+            return true;
+        };
+        package_dirs.iter().any(|dir| file.starts_with(dir))
+            || self.ancestor_dirs.iter().any(|dir| {
+                file.parent() == Some(dir.as_path()) || file.starts_with(dir.join(".cargo"))
+            })
+    }
+}
+
+/// like in `.gitignore`, a glob without a `/` matches a file name at any depth,
+/// otherwise it's matched against the whole path, relative to the root of the repository
+fn allowlist(patterns: &[String]) -> eyre::Result<globset::GlobSet> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let globs = if pattern.contains('/') {
+            vec![pattern.clone()]
+        } else {
+            vec![pattern.clone(), format!("**/{pattern}")]
+        };
+        for glob in globs {
+            builder.add(
+                globset::GlobBuilder::new(&glob)
+                    .literal_separator(true)
+                    .build()
+                    .wrap_err_with(|| format!("invalid glob of dirty files `{pattern}`"))?,
+            );
+        }
+    }
+    Ok(builder.build()?)
+}
+
+fn check(scope: &Scope) -> eyre::Result<()> {
+    let repo = git2::Repository::open(&scope.repo_root)?;
+    let mut changed_files = Vec::new();
     // Include each submodule so that the error message can provide
     // specifically *which* files in a submodule are modified.
-    status_submodules(&repo, &mut dirty_files)?;
+    status_submodules(&repo, &mut changed_files)?;
+
+    let (mut dirty_files, mut allowlisted, mut unrelated) = (vec![], 0, 0);
+    for file in changed_files {
+        match scope.classify(&file) {
+            Classified::Dirty => dirty_files.push(file),
+            Classified::Allowlisted => allowlisted += 1,
+            Classified::Unrelated => unrelated += 1,
+        }
+    }
+    if unrelated > 0 {
+        println!(
+            "{} {} {}",
+            "ignoring".cyan(),
+            unrelated,
+            "changed files outside of the contract's crate and its path dependencies, as they can't affect the build"
+                .cyan()
+        );
+    }
+    if allowlisted > 0 {
+        println!(
+            "{} {} {}",
+            "ignoring".cyan(),
+            allowlisted,
+            "changed files, matching `dirty_allowlist` or `--allow-dirty` globs".cyan()
+        );
+    }
 
     if dirty_files.is_empty() {
         return Ok(());
     }
     Err(eyre::eyre!(
         "{} files in the working directory contain changes that were \
-             not yet committed into git:\n\n{}",
+             not yet committed into git:\n\n{}\n\n\
+             Files, which are irrelevant to the build, can be allowed with globs in `dirty_allowlist` \
+             of `[package.metadata.near.reproducible_build]` or with `--allow-dirty <glob>` flag",
         dirty_files.len(),
         dirty_files
             .iter()
-            .map(to_string)
-            .collect::<Result<Vec<_>, _>>()
-            .wrap_err("Error parsing PathBaf")?
+            .map(|file| format!("{:?}", file.as_str()))
+            .collect::<Vec<_>>()
             .join("\n")
     ))
 }
@@ -62,7 +201,7 @@ fn check(repo_root: &camino::Utf8PathBuf) -> eyre::Result<()> {
 // Helper to collect dirty statuses while recursing into submodules.
 fn status_submodules(
     repo: &git2::Repository,
-    dirty_files: &mut Vec<std::path::PathBuf>,
+    dirty_files: &mut Vec<camino::Utf8PathBuf>,
 ) -> eyre::Result<()> {
     collect_statuses(repo, dirty_files)?;
     for submodule in repo.submodules()? {
@@ -78,25 +217,28 @@ fn status_submodules(
 // Helper to collect dirty statuses for a single repo.
 fn collect_statuses(
     repo: &git2::Repository,
-    dirty_files: &mut Vec<std::path::PathBuf>,
+    dirty_files: &mut Vec<camino::Utf8PathBuf>,
 ) -> eyre::Result<()> {
     let mut status_opts = git2::StatusOptions::new();
     // Exclude submodules, as they are being handled manually by recursing
     // into each one so that details about specific files can be
     // retrieved.
+    // Untracked directories are listed file by file, so that globs can be matched against them.
     status_opts
         .exclude_submodules(true)
         .include_ignored(true)
-        .include_untracked(true);
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
     let repo_statuses = repo.statuses(Some(&mut status_opts)).with_context(|| {
         format!(
             "Failed to retrieve git status from repo {}",
             repo.path().display()
         )
     })?;
-    let workdir = repo
-        .workdir()
-        .wrap_err("no workdir discovered in bare repository")?;
+    let workdir = super::canonicalize(
+        repo.workdir()
+            .wrap_err("no workdir discovered in bare repository")?,
+    )?;
     let this_dirty = repo_statuses.iter().filter_map(|entry| {
         let path = entry.path().expect("valid utf-8 path");
         if entry.status() == git2::Status::IGNORED {
@@ -107,3 +249,44 @@ fn collect_statuses(
     dirty_files.extend(this_dirty);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_outside_of_packages_and_allowlisted_ones_are_not_dirty() {
+        let scope = Scope {
+            repo_root: "/repo".into(),
+            allowlist: allowlist(&["*.log".to_string(), "contracts/a/notes/**".to_string()])
+                .unwrap(),
+            package_dirs: Some(vec!["/repo/contracts/a".into(), "/repo/libs/common".into()]),
+            ancestor_dirs: vec![
+                "/repo/contracts/a".into(),
+                "/repo/contracts".into(),
+                "/repo".into(),
+            ],
+        };
+        for (file, expected) in [
+            ("/repo/contracts/a/src/lib.rs", Classified::Dirty),
+            ("/repo/libs/common/src/lib.rs", Classified::Dirty),
+            ("/repo/Cargo.lock", Classified::Dirty),
+            ("/repo/.cargo/config.toml", Classified::Dirty),
+            ("/repo/contracts/a/build.log", Classified::Allowlisted),
+            ("/repo/contracts/a/notes/todo/1.md", Classified::Allowlisted),
+            ("/repo/contracts/b/src/lib.rs", Classified::Unrelated),
+            ("/repo/frontend/wip.ts", Classified::Unrelated),
+        ] {
+            assert_eq!(scope.classify(file.into()), expected, "{file}");
+        }
+
+        let scope = Scope {
+            package_dirs: None,
+            ..scope
+        };
+        assert_eq!(
+            scope.classify("/repo/frontend/wip.ts".into()),
+            Classified::Dirty
+        );
+    }
+}
@@ -1,2 +1,47 @@
+pub mod dependencies;
 pub mod dirty;
 pub mod pushed_to_remote;
+
+/// upper bound of credentials requests per connection, as libgit2 keeps asking for them
+/// after a rejected attempt
+const MAX_CREDENTIALS_ATTEMPTS: usize = 3;
+
+/// callbacks, providing credentials for private remotes: keys of ssh agent for `ssh://` urls,
+/// git credential helpers for `https://` ones
+pub(crate) fn remote_callbacks<'a>() -> git2::RemoteCallbacks<'a> {
+    let mut callbacks = git2::RemoteCallbacks::new();
+    let mut attempts = 0;
+    callbacks.credentials(move |url, username_from_url, allowed_types| {
+        attempts += 1;
+        if attempts > MAX_CREDENTIALS_ATTEMPTS {
+            return Err(git2::Error::from_str(&format!(
+                "authentication to `{url}` failed, for `ssh://` remotes check that the key is added to ssh agent (`ssh-add -l`)"
+            )));
+        }
+        let username = username_from_url.unwrap_or("git");
+        if allowed_types.contains(git2::CredentialType::USERNAME) {
+            return git2::Cred::username(username);
+        }
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            return git2::Cred::ssh_key_from_agent(username);
+        }
+        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            let config = git2::Config::open_default()?;
+            return git2::Cred::credential_helper(&config, url, username_from_url);
+        }
+        git2::Cred::default()
+    });
+    callbacks
+}
+
+/// canonicalized path, comparable with paths of git workdirs and of cargo metadata;
+/// unlike [`crate::fs::force_canonicalize_dir`], no directory is created
+fn canonicalize(path: impl AsRef<std::path::Path>) -> eyre::Result<camino::Utf8PathBuf> {
+    use eyre::WrapErr;
+
+    let path = path.as_ref();
+    let canonicalized = dunce::canonicalize(path)
+        .wrap_err_with(|| format!("failed to canonicalize path: {}", path.display()))?;
+    camino::Utf8PathBuf::from_path_buf(canonicalized)
+        .map_err(|err| eyre::eyre!("failed to convert path {}", err.to_string_lossy()))
+}
@@ -1,45 +1,309 @@
 use colored::Colorize;
-use eyre::WrapErr;
 
+use crate::env_keys;
+
+const DEFAULT_ATTEMPTS: usize = 5;
 const BETWEEN_ATTEMPTS_SLEEP: std::time::Duration = std::time::Duration::from_millis(100);
 
+/// checks that `commit_id` has been pushed to `git_url` without cloning the remote:
+///
+/// 1. refs of the remote are listed (as with `git ls-remote`), the commit is found
+///    if it's the tip of one of its branches or tags;
+/// 2. otherwise branches and tags are fetched without checkout one at a time, starting from the default branch
+///    of the remote, then other branches and then tags, until the commit is found in the history of one of them.
+///
+/// the commit isn't fetched by its id, as a remote may serve commits, which aren't reachable from
+/// its branches and tags, e.g. GitHub does so for ones of `refs/pull/*` or of forks of the repository
+///
+/// failures to reach the remote are retried up to [`env_keys::CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS`] times
 pub fn check(git_url: &url::Url, commit_id: git2::Oid) -> eyre::Result<()> {
-    for attempt in 1..=5 {
-        let tmp_clone_destination = tempfile::tempdir()?;
+    check_with_attempts(git_url, commit_id, attempts()?)
+}
+
+fn attempts() -> eyre::Result<usize> {
+    match std::env::var(env_keys::CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS) {
+        Ok(value) => match value.parse::<usize>() {
+            Ok(attempts) if attempts > 0 => Ok(attempts),
+            _ => Err(eyre::eyre!(
+                "`{}` environment variable should be a positive number of attempts, got `{}`",
+                env_keys::CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS,
+                value
+            )),
+        },
+        Err(_) => Ok(DEFAULT_ATTEMPTS),
+    }
+}
+
+#[derive(Debug)]
+enum Outcome {
+    /// description of the way the commit was found
+    Found(String),
+    /// names of refs of the remote, which have been checked
+    NotFound(Vec<String>),
+}
+
+fn check_with_attempts(
+    git_url: &url::Url,
+    commit_id: git2::Oid,
+    attempts: usize,
+) -> eyre::Result<()> {
+    let mut last_error = None;
+    for attempt in 1..=attempts {
         println!(
-            " {} `{}` -> `{:?}`",
-            format!("Clone attempt {attempt}:").green(),
+            " {} `{}`",
+            format!("Attempt {attempt}/{attempts}, looking up {commit_id} in").green(),
             git_url,
-            tmp_clone_destination
         );
-        let repo = git2::Repository::clone_recurse(git_url.as_str(), tmp_clone_destination.path());
-
-        match repo {
-            Ok(repo) => {
-                println!(" {}", "Checking if HEAD is present...".green());
-                repo.find_commit(commit_id)
-                    .wrap_err("commit wasn't found in remote repo. \
-                        Please, push the changes to the remote repository so reproducible builds become possible")?;
-                println!(
-                    " {} {} in `{}` -> `{}`",
-                    "commit was found in repo:".green(),
+        match lookup(git_url, commit_id) {
+            Ok(Outcome::Found(how)) => {
+                println!(" {} {}", "commit was found in remote:".green(), how);
+                return Ok(());
+            }
+            Ok(Outcome::NotFound(refs)) => {
+                return Err(eyre::eyre!(
+                    "commit {} wasn't found in `{}`.\n\
+                    It isn't the tip of any of the checked refs and isn't reachable from them:\n{}\n\
+                    Please, push the changes to the remote repository so reproducible builds become possible",
                     commit_id,
                     git_url,
-                    repo.path().display(),
-                );
-                return Ok(());
+                    format_refs(&refs),
+                ));
             }
             Err(err) => {
-                println!(" {} {:?}", "Encountered error:".yellow(), err,);
+                println!(" {} {:#}", "Encountered error:".yellow(), err);
+                last_error = Some(err);
                 std::thread::sleep(BETWEEN_ATTEMPTS_SLEEP);
             }
         }
     }
 
     Err(eyre::eyre!(
-        "Failed to verify that HEAD was pushed by cloning {}. Exceeded max attempts.\n\
-        Try setting `package.repository` \
-        of your contract to point to the remote of your repository.",
-        git_url
+        "Failed to verify that HEAD was pushed to `{}`. Exceeded max attempts ({}), last error: {:#}\n\
+        Try setting `package.repository` of your contract to point to the remote of your repository, \
+        or `{}` environment variable to a larger number of attempts.",
+        git_url,
+        attempts,
+        last_error.unwrap_or_else(|| eyre::eyre!("none")),
+        env_keys::CARGO_NEAR_GIT_REMOTE_CHECK_ATTEMPTS,
     ))
 }
+
+fn format_refs(refs: &[String]) -> String {
+    if refs.is_empty() {
+        return "  (remote has no branches or tags)".to_string();
+    }
+    refs.iter()
+        .map(|name| format!("  - {name}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// only branches and tags are fetched by `git clone` of verifiers,
+/// so e.g. `refs/pull/*` of GitHub aren't taken into account;
+/// `HEAD` of the remote is one of its branches
+fn is_cloned_ref(name: &str) -> bool {
+    name.starts_with("refs/heads/") || name.starts_with("refs/tags/")
+}
+
+fn fetch_options<'a>() -> git2::FetchOptions<'a> {
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options
+        .remote_callbacks(super::remote_callbacks())
+        .download_tags(git2::AutotagOption::None)
+        .update_fetchhead(false);
+    fetch_options
+}
+
+fn lookup(git_url: &url::Url, commit_id: git2::Oid) -> eyre::Result<Outcome> {
+    let tmp_dir = tempfile::tempdir()?;
+    let repo = git2::Repository::init_bare(tmp_dir.path())?;
+    let mut remote = repo.remote_anonymous(git_url.as_str())?;
+
+    let (refs, default_branch) = {
+        let connection = remote.connect_auth(
+            git2::Direction::Fetch,
+            Some(super::remote_callbacks()),
+            None,
+        )?;
+        let default_branch = connection
+            .default_branch()
+            .ok()
+            .and_then(|name| name.as_str().map(str::to_string));
+        let refs = connection
+            .list()?
+            .iter()
+            .filter(|head| is_cloned_ref(head.name()))
+            .map(|head| (head.name().to_string(), head.oid()))
+            .collect::<Vec<_>>();
+        (refs, default_branch)
+    };
+    if let Some((name, _)) = refs.iter().find(|(_, oid)| *oid == commit_id) {
+        return Ok(Outcome::Found(format!("tip of `{name}`")));
+    }
+    let names = refs
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+
+    let mut ordered = refs.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|(name, _)| {
+        (
+            Some(name) != default_branch.as_ref(),
+            name.starts_with("refs/tags/"),
+        )
+    });
+    for (index, (name, oid)) in ordered.into_iter().enumerate() {
+        // history of a tip, which is already present, has been fetched with one of previous refs
+        if repo.find_object(*oid, None).is_err() {
+            remote.fetch(
+                &[format!("+{name}:refs/checked/{index}")],
+                Some(&mut fetch_options()),
+                None,
+            )?;
+        }
+        if repo.find_commit(commit_id).is_err() {
+            continue;
+        }
+        let Ok(tip) = repo
+            .find_object(*oid, None)
+            .and_then(|tip| tip.peel_to_commit())
+        else {
+            continue;
+        };
+        if repo.graph_descendant_of(tip.id(), commit_id)? {
+            return Ok(Outcome::Found(format!("ancestor of `{name}`")));
+        }
+    }
+    Ok(Outcome::NotFound(names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &git2::Repository, message: &str) -> git2::Oid {
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            parent.iter().collect::<Vec<_>>().as_slice(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn commit_is_looked_up_in_local_bare_remote() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let remote_path = tmp_dir.path().join("remote.git");
+        git2::Repository::init_bare(&remote_path).unwrap();
+        let local = git2::Repository::init(tmp_dir.path().join("local")).unwrap();
+
+        let first = commit(&local, "first");
+        let second = commit(&local, "second");
+        let branch = local.head().unwrap().name().unwrap().to_string();
+        local
+            .remote_anonymous(remote_path.to_str().unwrap())
+            .unwrap()
+            .push(&[format!("{branch}:{branch}")], None)
+            .unwrap();
+        let unpushed = commit(&local, "unpushed");
+
+        let git_url = url::Url::from_file_path(&remote_path).unwrap();
+        assert!(matches!(
+            lookup(&git_url, second).unwrap(),
+            Outcome::Found(how) if how == format!("tip of `{branch}`")
+        ));
+        assert!(matches!(
+            lookup(&git_url, first).unwrap(),
+            Outcome::Found(_)
+        ));
+        assert!(matches!(
+            lookup(&git_url, unpushed).unwrap(),
+            Outcome::NotFound(refs) if refs == vec![branch.clone()]
+        ));
+
+        let err = check_with_attempts(&git_url, unpushed, 1).unwrap_err();
+        assert!(err.to_string().contains(&format!("  - {branch}")));
+        let missing_remote = url::Url::from_file_path(tmp_dir.path().join("missing.git")).unwrap();
+        let err = check_with_attempts(&missing_remote, unpushed, 2).unwrap_err();
+        assert!(err.to_string().contains("Exceeded max attempts (2)"));
+    }
+
+    #[test]
+    fn default_branch_is_looked_up_first() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let remote_path = tmp_dir.path().join("remote.git");
+        git2::Repository::init_bare(&remote_path).unwrap();
+        let local = git2::Repository::init(tmp_dir.path().join("local")).unwrap();
+
+        let first = commit(&local, "first");
+        commit(&local, "second");
+        let branch = local.head().unwrap().name().unwrap().to_string();
+        let mut push_remote = local
+            .remote_anonymous(remote_path.to_str().unwrap())
+            .unwrap();
+        push_remote
+            .push(&[format!("{branch}:{branch}")], None)
+            .unwrap();
+        commit(&local, "feature");
+        // listed before the default branch by the remote
+        push_remote
+            .push(&[format!("{branch}:refs/heads/a-feature")], None)
+            .unwrap();
+
+        let git_url = url::Url::from_file_path(&remote_path).unwrap();
+        assert!(matches!(
+            lookup(&git_url, first).unwrap(),
+            Outcome::Found(how) if how == format!("ancestor of `{branch}`")
+        ));
+    }
+
+    #[test]
+    fn commit_only_on_non_branch_ref_is_not_found() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let remote_path = tmp_dir.path().join("remote.git");
+        let remote = git2::Repository::init_bare(&remote_path).unwrap();
+        // the remote serves any commit in its object database by id, as GitHub does
+        remote
+            .config()
+            .unwrap()
+            .set_bool("uploadpack.allowAnySHA1InWant", true)
+            .unwrap();
+        let local = git2::Repository::init(tmp_dir.path().join("local")).unwrap();
+
+        commit(&local, "first");
+        let branch = local.head().unwrap().name().unwrap().to_string();
+        let mut push_remote = local
+            .remote_anonymous(remote_path.to_str().unwrap())
+            .unwrap();
+        push_remote
+            .push(&[format!("{branch}:{branch}")], None)
+            .unwrap();
+        let pull_request = commit(&local, "pull request");
+        push_remote
+            .push(&[format!("{branch}:refs/pull/1/head")], None)
+            .unwrap();
+        assert!(remote.find_commit(pull_request).is_ok());
+
+        let git_url = url::Url::from_file_path(&remote_path).unwrap();
+        let fetched = git2::Repository::init_bare(tmp_dir.path().join("fetched.git")).unwrap();
+        fetched
+            .remote_anonymous(git_url.as_str())
+            .unwrap()
+            .fetch(&[pull_request.to_string()], None, None)
+            .unwrap();
+        assert!(fetched.find_commit(pull_request).is_ok());
+
+        assert!(matches!(
+            lookup(&git_url, pull_request).unwrap(),
+            Outcome::NotFound(refs) if refs == vec![branch.clone()]
+        ));
+    }
+}
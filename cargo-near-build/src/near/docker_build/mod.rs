@@ -1,14 +1,22 @@
 use colored::Colorize;
-use near_verify_rs::logic::docker_checks;
 
+use crate::camino;
 use crate::docker::DockerBuildOpts;
 use crate::pretty_print;
+use crate::types::cargo::manifest_path::{MANIFEST_FILE_NAME, ManifestPath};
+use crate::types::cargo::metadata::CrateMetadata;
+use crate::types::near::build::checksum::SHA256Checksum;
+use crate::types::near::build::common_buildtime_env::CargoTargetDir;
 use crate::types::near::build::input::BuildContext;
 use crate::types::near::build::output::CompilationArtifact;
+use crate::types::near::docker_build::container_runtime::ContainerRuntime;
 use crate::types::near::docker_build::subprocess::nep330_build_info::BuildInfoMixed;
-use crate::types::near::docker_build::{cloned_repo, crate_in_repo, metadata};
+use crate::types::near::docker_build::{cloned_repo, crate_in_repo, metadata, vendor};
 
+mod container_runtime;
 pub mod git_checks;
+pub mod upgrade_image;
+pub mod verify;
 pub mod warn_versions_upgrades;
 
 const RUST_LOG_EXPORT: &str = "RUST_LOG=info";
@@ -16,24 +24,226 @@ const RUST_LOG_EXPORT: &str = "RUST_LOG=info";
 pub fn run(opts: DockerBuildOpts, quiet: bool) -> eyre::Result<CompilationArtifact> {
     let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
     color.apply();
-    let crate_in_repo = pretty_print::handle_step(
-        "Opening repo and determining HEAD and relative path of contract...",
-        || crate_in_repo::Crate::find(&opts.contract_path()?),
+    let cloned_repo = clone_repo(&opts)?;
+    let applied_build_meta = apply_build_meta(&opts, &cloned_repo)?;
+    let vendored_sources = prepare_build_site(&opts, &applied_build_meta, &cloned_repo)?;
+
+    let runtime = ContainerRuntime::resolve(opts.container_runtime)?;
+    let docker_build_out_wasm = build_in_container(
+        &opts,
+        &applied_build_meta,
+        &cloned_repo,
+        vendored_sources.as_ref(),
+        runtime,
+        quiet,
     )?;
-    pretty_print::handle_step("Checking if git is dirty...", || {
-        git_checks::dirty::check_then_handle(opts.context, &crate_in_repo.repo_root)
-    })?;
-    let cloned_repo = pretty_print::handle_step(
-        "Cloning project repo to a temporary build site, removing uncommitted changes...",
+
+    cloned_repo.copy_artifact(docker_build_out_wasm, opts.out_dir.clone(), None)
+}
+
+/// builds default `[package.metadata.near.reproducible_build]` and every one of its variants
+/// one after another from a single clone of the repository, `opts.variant` is ignored
+///
+/// artifact of a variant is named `<contract>.<variant>.wasm`, the one of default section keeps its name
+pub fn run_all_variants(
+    opts: DockerBuildOpts,
+    quiet: bool,
+) -> eyre::Result<Vec<(Option<String>, CompilationArtifact)>> {
+    let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
+    color.apply();
+    let cloned_repo = clone_repo(&opts)?;
+    let variant_names = metadata::parse::ReproducibleBuild::parse(cloned_repo.crate_metadata())?
+        .variants_map
+        .into_keys()
+        .map(Some);
+    // all variants are validated before the first, slow, build
+    let variants = std::iter::once(None)
+        .chain(variant_names)
+        .map(|variant| {
+            let variant_opts = DockerBuildOpts {
+                variant,
+                ..opts.clone()
+            };
+            let applied_build_meta = apply_build_meta(&variant_opts, &cloned_repo)?;
+            Ok((variant_opts, applied_build_meta))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    let (_, default_build_meta) = variants.first().expect("default section is always present");
+    let vendored_sources = prepare_build_site(&opts, default_build_meta, &cloned_repo)?;
+
+    let runtime = ContainerRuntime::resolve(opts.container_runtime)?;
+    let mut artifacts = vec![];
+    for (variant_opts, applied_build_meta) in variants {
+        let section_name = metadata::section_name(variant_opts.variant.as_ref());
+        pretty_print::step(&format!("Building `{}` ...", section_name.magenta()));
+        // `cargo` doesn't track environment variables, read by proc-macros, so a build with
+        // a previous variant's NEP-330 build info could be partially reused
+        cloned_repo.clean_target_dir()?;
+        let docker_build_out_wasm = build_in_container(
+            &variant_opts,
+            &applied_build_meta,
+            &cloned_repo,
+            vendored_sources.as_ref(),
+            runtime,
+            quiet,
+        )?;
+        let artifact = cloned_repo.copy_artifact(
+            docker_build_out_wasm,
+            opts.out_dir.clone(),
+            variant_opts.variant.as_deref(),
+        )?;
+        artifacts.push((variant_opts.variant, artifact));
+    }
+
+    print_variants_summary(&artifacts)?;
+    Ok(artifacts)
+}
+
+fn print_variants_summary(artifacts: &[(Option<String>, CompilationArtifact)]) -> eyre::Result<()> {
+    let rows = artifacts
+        .iter()
+        .map(|(variant, artifact)| {
+            let checksum = artifact.compute_hash()?;
+            Ok((
+                variant.as_deref().unwrap_or("<DEFAULT>"),
+                checksum.to_hex_string(),
+                checksum.to_base58_string(),
+                artifact.path.as_str(),
+            ))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    let width = rows
+        .iter()
+        .map(|(variant, ..)| variant.len())
+        .max()
+        .unwrap_or_default();
+
+    println!();
+    println!("{}", "Built variants:".green().bold());
+    for (variant, hex, base58, path) in rows {
+        println!(
+            "    {:<width$}  sha256: {}  base58: {}  {}",
+            variant.yellow(),
+            hex,
+            base58,
+            path.cyan()
+        );
+    }
+    Ok(())
+}
+
+fn clone_repo(opts: &DockerBuildOpts) -> eyre::Result<cloned_repo::ClonedRepo> {
+    Ok(match opts.remote.as_ref() {
+        Some(remote) => {
+            let cloned_repo = pretty_print::handle_step(
+                &format!(
+                    "Cloning `{}` at `{}` to a temporary build site...",
+                    remote.repo.magenta(),
+                    remote.rev.magenta()
+                ),
+                || {
+                    cloned_repo::ClonedRepo::check_locked_then_clone_remote(
+                        remote,
+                        opts.no_locked,
+                        opts.context,
+                    )
+                },
+            )?;
+            check_dependencies_of_clone(&cloned_repo)?;
+            cloned_repo
+        }
+        None => {
+            let crate_in_repo = pretty_print::handle_step(
+                "Opening repo and determining HEAD and relative path of contract...",
+                || crate_in_repo::Crate::find(&opts.contract_path()?),
+            )?;
+            match opts.git_ref.as_deref() {
+                Some(git_ref) => {
+                    let cloned_repo = pretty_print::handle_step(
+                        &format!(
+                            "Cloning project repo at `{}` to a temporary build site...",
+                            git_ref.magenta()
+                        ),
+                        || {
+                            cloned_repo::ClonedRepo::check_locked_then_clone(
+                                crate_in_repo,
+                                opts.no_locked,
+                                opts.context,
+                                Some(git_ref),
+                            )
+                        },
+                    )?;
+                    check_dependencies_of_clone(&cloned_repo)?;
+                    cloned_repo
+                }
+                None => {
+                    let crate_metadata =
+                        ManifestPath::try_from(crate_in_repo.crate_root.join(MANIFEST_FILE_NAME))
+                            .and_then(|manifest_path| {
+                                CrateMetadata::collect(
+                                    manifest_path,
+                                    opts.no_locked,
+                                    &CargoTargetDir::NoOp,
+                                    None,
+                                )
+                            });
+                    pretty_print::handle_step("Checking if git is dirty...", || {
+                        let scope = git_checks::dirty::Scope::collect(
+                            &crate_in_repo,
+                            crate_metadata.as_ref(),
+                            &opts.allow_dirty,
+                        )?;
+                        git_checks::dirty::check_then_handle(opts.context, &scope)
+                    })?;
+                    if let Ok(crate_metadata) = &crate_metadata {
+                        pretty_print::handle_step(
+                            "Checking that dependencies of the contract will be present on the temporary build site...",
+                            || {
+                                git_checks::dependencies::check(
+                                    crate_metadata,
+                                    &crate_in_repo.repo_root,
+                                    crate_in_repo.head,
+                                )
+                            },
+                        )?;
+                    }
+                    pretty_print::handle_step(
+                        "Cloning project repo to a temporary build site, removing uncommitted changes...",
+                        || {
+                            cloned_repo::ClonedRepo::check_locked_then_clone(
+                                crate_in_repo,
+                                opts.no_locked,
+                                opts.context,
+                                None,
+                            )
+                        },
+                    )?
+                }
+            }
+        }
+    })
+}
+
+/// metadata of the local crate may differ from the one at `--git-ref` and there's no local crate
+/// for `--repo`, so dependencies are checked against the clone at the built revision
+fn check_dependencies_of_clone(cloned_repo: &cloned_repo::ClonedRepo) -> eyre::Result<()> {
+    let repo_root = camino::Utf8PathBuf::try_from(cloned_repo.tmp_repo_dir.path().to_path_buf())?;
+    pretty_print::handle_step(
+        "Checking that dependencies of the contract are present at the built revision...",
         || {
-            cloned_repo::ClonedRepo::check_locked_then_clone(
-                crate_in_repo.clone(),
-                opts.no_locked,
-                opts.context,
+            git_checks::dependencies::check(
+                cloned_repo.crate_metadata(),
+                &repo_root,
+                cloned_repo.initial_crate_in_repo.head,
             )
         },
-    )?;
+    )
+}
 
+fn apply_build_meta(
+    opts: &DockerBuildOpts,
+    cloned_repo: &cloned_repo::ClonedRepo,
+) -> eyre::Result<metadata::AppliedReproducibleBuild> {
     let applied_build_meta = {
         let docker_build_meta_parsed = pretty_print::handle_step(
             &format!(
@@ -71,25 +281,28 @@ pub fn run(opts: DockerBuildOpts, quiet: bool) -> eyre::Result<CompilationArtifa
         &applied_build_meta,
         near_sdk_support,
     );
-    let contract_source_metadata = {
-        let local_crate_info = BuildInfoMixed::new(&opts, &applied_build_meta, &cloned_repo)?;
-        near_verify_rs::types::contract_source_metadata::ContractSourceMetadata::from(
-            local_crate_info,
-        )
-    };
+    Ok(applied_build_meta)
+}
 
+/// checks, which don't depend on a variant of the build, and vendoring of dependencies;
+/// returns checksum of vendored sources, if dependencies were vendored
+fn prepare_build_site(
+    opts: &DockerBuildOpts,
+    applied_build_meta: &metadata::AppliedReproducibleBuild,
+    cloned_repo: &cloned_repo::ClonedRepo,
+) -> eyre::Result<Option<SHA256Checksum>> {
     if let BuildContext::Deploy {
         skip_git_remote_check,
     } = opts.context
     {
         if !skip_git_remote_check {
             pretty_print::handle_step(
-                "Performing check that current HEAD has been pushed to remote...",
+                "Performing check that built revision has been pushed to remote...",
                 || {
                     git_checks::pushed_to_remote::check(
                         // this unwrap depends on `metadata::AppliedReproducibleBuild::validate` logic
                         &applied_build_meta.repository.clone().unwrap(),
-                        crate_in_repo.head,
+                        cloned_repo.initial_crate_in_repo.head,
                     )
                 },
             )?;
@@ -100,31 +313,119 @@ pub fn run(opts: DockerBuildOpts, quiet: bool) -> eyre::Result<CompilationArtifa
             )?;
         }
     }
-    if std::env::var(near_verify_rs::env_keys::nonspec::SERVER_DISABLE_INTERACTIVE).is_err() {
-        pretty_print::handle_step("Performing `docker` sanity check...", || {
-            docker_checks::sanity::check(quiet)
-        })?;
-
-        pretty_print::handle_step("Checking that specified image is available...", || {
-            let docker_image = applied_build_meta.concat_image();
-            docker_checks::pull_image::check(&docker_image, quiet)
-        })?;
+    if !opts.vendor {
+        return Ok(None);
     }
+    let vendored_sources = pretty_print::handle_step(
+        "Vendoring dependencies on the temporary build site for a build without network access...",
+        || cloned_repo.vendor(),
+    )?;
+    Ok(Some(vendored_sources))
+}
 
-    pretty_print::step("Running build in docker command step...");
-    let out_dir_arg = opts.out_dir.clone();
+fn build_in_container(
+    opts: &DockerBuildOpts,
+    applied_build_meta: &metadata::AppliedReproducibleBuild,
+    cloned_repo: &cloned_repo::ClonedRepo,
+    vendored_sources: Option<&SHA256Checksum>,
+    runtime: ContainerRuntime,
+    quiet: bool,
+) -> eyre::Result<camino::Utf8PathBuf> {
+    let local_crate_info =
+        BuildInfoMixed::new(opts, applied_build_meta, cloned_repo, vendored_sources)?;
+    let contract_source_metadata =
+        near_verify_rs::types::contract_source_metadata::ContractSourceMetadata::from(
+            local_crate_info,
+        );
+    check_docker(runtime, &applied_build_meta.concat_image(), quiet)?;
 
+    pretty_print::step("Running build in docker command step...");
     contract_source_metadata.validate(None)?;
-    let docker_build_out_wasm = near_verify_rs::logic::nep330_build::run(
-        contract_source_metadata,
+    let docker_build_out_wasm = container_runtime::run_build(
+        runtime,
+        &contract_source_metadata,
         cloned_repo.contract_source_workdir()?,
-        additional_docker_args(),
+        additional_docker_args(runtime, opts.vendor),
         quiet,
     )?;
+    if let Some(vendored_sources) = vendored_sources {
+        print_vendored_build_note(vendored_sources);
+    }
+    Ok(docker_build_out_wasm)
+}
+
+/// vendored sources are compiled from `cargo-near-vendor` directory of the crate instead of cargo
+/// registry, and these paths end up in the wasm, e.g. in panic messages, so its hash doesn't match
+/// the one of the same build with network access; vendoring is recorded in `build_command` of NEP-330
+/// build info, so that `cargo near verify` replays it
+fn print_vendored_build_note(vendored_sources: &SHA256Checksum) {
+    println!();
+    println!(
+        "{} {}",
+        "built with vendored dependencies, sha256 of vendored sources:".cyan(),
+        vendored_sources.to_hex_string()
+    );
+    println!(
+        "{}",
+        format!(
+            "vendoring is recorded as `--env {}=..` in `build_command` of NEP-330 build info; \
+             wasm hash of a vendored build differs from the one of a build with network access, \
+             so it's reproduced by `cargo near verify`, which vendors dependencies again, \
+             and not by verifiers, which only run `build_command`",
+            vendor::BUILD_COMMAND_ENV_KEY
+        )
+        .yellow()
+    );
+}
 
-    cloned_repo.copy_artifact(docker_build_out_wasm, out_dir_arg)
+/// prints default `[package.metadata.near.reproducible_build]` of the local crate at
+/// `opts.manifest_path` and every one of its variants, with `extends` resolved
+pub fn list_variants(opts: DockerBuildOpts) -> eyre::Result<()> {
+    let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
+    color.apply();
+    let crate_metadata = pretty_print::handle_step("Collecting cargo project metadata...", || {
+        let manifest_path = ManifestPath::try_from(opts.contract_path()?.join(MANIFEST_FILE_NAME))?;
+        CrateMetadata::collect(manifest_path, opts.no_locked, &CargoTargetDir::NoOp, None)
+    })?;
+    let applied_variants =
+        metadata::parse::ReproducibleBuild::parse(&crate_metadata)?.resolve_all()?;
+    for applied_variant in applied_variants {
+        let section_name = metadata::section_name(applied_variant.selected_variant.as_ref());
+        println!("{}", section_name.magenta());
+        print!("{applied_variant}");
+        if let Err(err) = applied_variant.validate() {
+            println!("    {}: {}", "invalid".red(), err);
+        }
+        println!();
+    }
+    Ok(())
 }
 
-fn additional_docker_args() -> Vec<String> {
-    vec!["--env".to_string(), RUST_LOG_EXPORT.to_string()]
+/// checks that container `runtime` is usable and `docker_image` is available,
+/// unless interactivity is disabled by [`near_verify_rs::env_keys::nonspec::SERVER_DISABLE_INTERACTIVE`]
+pub(crate) fn check_docker(
+    runtime: ContainerRuntime,
+    docker_image: &str,
+    quiet: bool,
+) -> eyre::Result<()> {
+    if std::env::var(near_verify_rs::env_keys::nonspec::SERVER_DISABLE_INTERACTIVE).is_err() {
+        pretty_print::handle_step(&format!("Performing `{runtime}` sanity check..."), || {
+            container_runtime::sanity_check(runtime, quiet)
+        })?;
+
+        pretty_print::handle_step("Checking that specified image is available...", || {
+            container_runtime::pull_image(runtime, docker_image, quiet)
+        })?;
+    }
+    Ok(())
+}
+
+/// `offline` disables networking of the container
+pub(crate) fn additional_docker_args(runtime: ContainerRuntime, offline: bool) -> Vec<String> {
+    let mut args = vec!["--env".to_string(), RUST_LOG_EXPORT.to_string()];
+    args.extend(runtime.additional_run_args());
+    if offline {
+        args.push("--network=none".to_string());
+    }
+    args
 }
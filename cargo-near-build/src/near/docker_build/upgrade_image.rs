@@ -0,0 +1,427 @@
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+
+use crate::pretty_print;
+use crate::types::cargo::manifest_path::MANIFEST_FILE_NAME;
+use crate::types::near::docker_build::metadata;
+use crate::types::near::docker_build::upgrade_image::{ImageTag, Opts};
+
+use super::warn_versions_upgrades::{DOCKER_IMAGE_REGEX_PATTERN, PROD_IMAGE};
+
+/// docker image, which tags are looked up and upgraded to
+pub const SOURCE_SCAN_IMAGE: &str = PROD_IMAGE;
+
+/// first `cargo-near` version of an image, which requires explicit `container_build_command`
+const CARGO_NEAR_BUILD_COMMAND_REQUIRED: cargo_metadata::semver::Version =
+    cargo_metadata::semver::Version::new(0, 13, 0);
+
+/// default `container_build_command` of an image with `cargo_near` version inside
+fn default_container_build_command(cargo_near: &cargo_metadata::semver::Version) -> Vec<String> {
+    let command: &[&str] = if *cargo_near >= CARGO_NEAR_BUILD_COMMAND_REQUIRED {
+        &[
+            "cargo",
+            "near",
+            "build",
+            "non-reproducible-wasm",
+            "--locked",
+        ]
+    } else {
+        &["cargo", "near", "build"]
+    };
+    command.iter().map(ToString::to_string).collect()
+}
+
+/// the latest of `tags` (pairs of tag and its digest), which match
+/// [`SOURCE_SCAN_TAG_PATTERN`](super::warn_versions_upgrades::SOURCE_SCAN_TAG_PATTERN)
+/// and `rust` version filter, e.g. `1.85` or `1.85.0`
+///
+/// tags are ordered by version of `cargo-near`, then by version of rust
+pub fn select_latest(
+    tags: impl IntoIterator<Item = (String, String)>,
+    rust: Option<&str>,
+) -> eyre::Result<ImageTag> {
+    let rust_filter = rust
+        .map(|rust| {
+            rust.split('.')
+                .map(|component| component.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .filter(|components| (1..=3).contains(&components.len()))
+                .wrap_err_with(|| format!("`{rust}` isn't a rust version, e.g. `1.85` or `1.85.0`"))
+        })
+        .transpose()?;
+    tags.into_iter()
+        .filter_map(|(tag, image_digest)| ImageTag::parse(&tag, &image_digest))
+        .filter(|tag| {
+            rust_filter.as_ref().is_none_or(|components| {
+                let version = [tag.rust.major, tag.rust.minor, tag.rust.patch];
+                version.starts_with(components)
+            })
+        })
+        .max_by(|a, b| (&a.cargo_near, &a.rust).cmp(&(&b.cargo_near, &b.rust)))
+        .wrap_err_with(|| match rust {
+            Some(rust) => format!("no `{SOURCE_SCAN_IMAGE}` tags found for rust `{rust}`"),
+            None => format!("no `{SOURCE_SCAN_IMAGE}` tags found"),
+        })
+}
+
+/// a change of a field of the section, made by [`upgrade_manifest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: String,
+}
+
+/// rewrites `image`, `image_digest` and, if it's the default one for current image,
+/// `container_build_command` of [`metadata::section_name`] of `variant` in `manifest`
+/// to ones of `tag`; formatting and comments of `manifest` are preserved
+pub fn upgrade_manifest(
+    manifest: &str,
+    variant: Option<&str>,
+    tag: &ImageTag,
+) -> eyre::Result<(String, Vec<Change>)> {
+    let section_name = metadata::section_name(variant.map(ToString::to_string).as_ref());
+    let mut document = manifest
+        .parse::<toml_edit::DocumentMut>()
+        .wrap_err("unable to parse Cargo.toml")?;
+    let default_section = document
+        .get_mut("package")
+        .and_then(|item| item.get_mut("metadata"))
+        .and_then(|item| item.get_mut("near"))
+        .and_then(|item| item.get_mut("reproducible_build"))
+        .wrap_err_with(|| format!("Missing `{}` in Cargo.toml", metadata::section_name(None)))?;
+    let default_image = default_section
+        .get("image")
+        .and_then(toml_edit::Item::as_str)
+        .map(ToString::to_string);
+    let section = match variant {
+        Some(variant) => default_section
+            .get_mut("variant")
+            .and_then(|item| item.get_mut(variant))
+            .wrap_err_with(|| format!("Missing `{section_name}` in Cargo.toml"))?,
+        None => default_section,
+    };
+    if !section.is_table_like() {
+        return Err(eyre::eyre!(
+            "Malformed `{section_name}` in Cargo.toml: not a table"
+        ));
+    }
+
+    let current_image = section
+        .get("image")
+        .and_then(toml_edit::Item::as_str)
+        .map(ToString::to_string)
+        .or(default_image);
+    let current_cargo_near = current_image.as_deref().and_then(cargo_near_version);
+
+    let mut changes = vec![];
+    let image = format!("{}:{}", SOURCE_SCAN_IMAGE, tag.tag);
+    changes.extend(set_string(section, "image", &image));
+    changes.extend(set_string(section, "image_digest", &tag.image_digest));
+
+    let new_command = default_container_build_command(&tag.cargo_near);
+    let current_command = section
+        .get("container_build_command")
+        .and_then(toml_edit::Item::as_array);
+    let update_command = match (current_command, &current_cargo_near) {
+        // an explicit command is required since 0.13.0, and default section has no fallback
+        (None, _) => variant.is_none() && tag.cargo_near >= CARGO_NEAR_BUILD_COMMAND_REQUIRED,
+        (Some(command), Some(cargo_near)) => {
+            array_of_strings(command).as_ref() == Some(&default_container_build_command(cargo_near))
+        }
+        (Some(_), None) => false,
+    };
+    if update_command {
+        changes.extend(set_command(section, &new_command));
+    }
+
+    Ok((document.to_string(), changes))
+}
+
+/// version of `cargo-near` of `image`, if it's the one of [`SOURCE_SCAN_IMAGE`]
+fn cargo_near_version(image: &str) -> Option<cargo_metadata::semver::Version> {
+    let regex = regex::Regex::new(DOCKER_IMAGE_REGEX_PATTERN).expect("no error");
+    let captures = regex.captures(image)?;
+    if captures.name("image")?.as_str() != SOURCE_SCAN_IMAGE {
+        return None;
+    }
+    ImageTag::parse(captures.name("tag")?.as_str(), "").map(|tag| tag.cargo_near)
+}
+
+fn array_of_strings(array: &toml_edit::Array) -> Option<Vec<String>> {
+    array
+        .iter()
+        .map(|value| value.as_str().map(ToString::to_string))
+        .collect()
+}
+
+/// sets `key` of table-like `section` to `new`, keeping comments around the old value
+fn set_string(section: &mut toml_edit::Item, key: &'static str, new: &str) -> Option<Change> {
+    match section.get_mut(key).and_then(toml_edit::Item::as_value_mut) {
+        Some(value) => {
+            let old = value.as_str().map(ToString::to_string);
+            if old.as_deref() == Some(new) {
+                return None;
+            }
+            let decor = value.decor().clone();
+            *value = toml_edit::Value::from(new);
+            *value.decor_mut() = decor;
+            Some(Change {
+                field: key,
+                old,
+                new: new.to_string(),
+            })
+        }
+        None => {
+            section[key] = toml_edit::value(new);
+            Some(Change {
+                field: key,
+                old: None,
+                new: new.to_string(),
+            })
+        }
+    }
+}
+
+/// sets `container_build_command` of table-like `section` to `command`, keeping
+/// one-element-per-line layout of the old array
+fn set_command(section: &mut toml_edit::Item, command: &[String]) -> Option<Change> {
+    const KEY: &str = "container_build_command";
+    let mut array = command.iter().collect::<toml_edit::Array>();
+    let old = match section.get_mut(KEY).and_then(toml_edit::Item::as_value_mut) {
+        Some(value) => {
+            let old_array = value.as_array()?;
+            if array_of_strings(old_array).as_deref() == Some(command) {
+                return None;
+            }
+            let multiline = old_array.iter().any(|element| {
+                element
+                    .decor()
+                    .prefix()
+                    .and_then(toml_edit::RawString::as_str)
+                    .is_some_and(|prefix| prefix.contains('\n'))
+            });
+            if multiline {
+                for element in array.iter_mut() {
+                    element.decor_mut().set_prefix("\n    ");
+                }
+                array.set_trailing("\n");
+                array.set_trailing_comma(true);
+            }
+            let old = format!("{old_array}");
+            let decor = value.decor().clone();
+            *value = toml_edit::Value::Array(array);
+            *value.decor_mut() = decor;
+            Some(old.trim().to_string())
+        }
+        None => {
+            section[KEY] = toml_edit::value(array);
+            None
+        }
+    };
+    Some(Change {
+        field: KEY,
+        old,
+        new: format!("{command:?}"),
+    })
+}
+
+/// upgrades `image` and `image_digest` of [`metadata::section_name`] of `opts.variant`
+/// in contract's `Cargo.toml` to `opts.tag`
+pub fn run(opts: Opts) -> eyre::Result<Vec<Change>> {
+    let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
+    color.apply();
+
+    let manifest_path = match opts.manifest_path {
+        Some(manifest_path) => manifest_path,
+        None => camino::Utf8PathBuf::from(MANIFEST_FILE_NAME),
+    };
+    let manifest = std::fs::read_to_string(&manifest_path)
+        .wrap_err_with(|| format!("unable to read `{manifest_path}`"))?;
+    let section_name = metadata::section_name(opts.variant.as_ref());
+    let (upgraded, changes) = pretty_print::handle_step(
+        &format!(
+            "Upgrading `{}` of `{}` to `{}:{}` ...",
+            section_name.magenta(),
+            manifest_path,
+            SOURCE_SCAN_IMAGE,
+            opts.tag.tag.magenta()
+        ),
+        || upgrade_manifest(&manifest, opts.variant.as_deref(), &opts.tag),
+    )?;
+
+    if changes.is_empty() {
+        println!(
+            "{}",
+            format!("`{section_name}` is already up to date").green()
+        );
+        return Ok(changes);
+    }
+    for change in &changes {
+        println!("    {}", format!("Changing {}:", change.field).yellow());
+        println!(
+            "        {} `{}`",
+            "old:".red(),
+            change.old.as_deref().unwrap_or("<ABSENT>")
+        );
+        println!("        {} `{}`", "new:".green(), change.new);
+        println!();
+    }
+    let downgraded = changes
+        .iter()
+        .find(|change| change.field == "image")
+        .and_then(|change| change.old.as_deref())
+        .and_then(cargo_near_version)
+        .filter(|current| *current > opts.tag.cargo_near);
+    if let Some(current) = downgraded {
+        println!(
+            "{}: {}",
+            "WARNING".red(),
+            format!(
+                "`cargo-near` {} of the selected image is older than {} of the previous one",
+                opts.tag.cargo_near, current
+            )
+            .yellow()
+        );
+        println!();
+    }
+    std::fs::write(&manifest_path, upgraded)
+        .wrap_err_with(|| format!("unable to write `{manifest_path}`"))?;
+    println!(
+        "{}",
+        format!("`{manifest_path}` is updated, don't forget to commit it").green()
+    );
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST_OLD: &str =
+        "sha256:7467038bdddc86484b73b416eeadce926ff59013e128e53dec5a19e1cb4b2234";
+    const DIGEST_NEW: &str =
+        "sha256:a9d8bee7b134856cc8baa142494a177f2ba9ecfededfcdd38f634e14cca8aae2";
+
+    fn tags() -> Vec<(String, String)> {
+        [
+            ("0.13.4-rust-1.85.0", "sha256:1"),
+            ("0.14.1-rust-1.85.0", "sha256:2"),
+            ("0.14.1-rust-1.86.0", "sha256:3"),
+            ("latest", "sha256:4"),
+            ("0.9.0-rust-1.90.0", "sha256:5"),
+        ]
+        .into_iter()
+        .map(|(tag, digest)| (tag.to_string(), digest.to_string()))
+        .collect()
+    }
+
+    #[test]
+    fn select_latest_orders_by_cargo_near_then_rust() {
+        assert_eq!(
+            select_latest(tags(), None).unwrap().tag,
+            "0.14.1-rust-1.86.0"
+        );
+        assert_eq!(
+            select_latest(tags(), Some("1.85")).unwrap().image_digest,
+            "sha256:2"
+        );
+        assert_eq!(
+            select_latest(tags(), Some("1.90.0")).unwrap().tag,
+            "0.9.0-rust-1.90.0"
+        );
+        assert!(select_latest(tags(), Some("1.70")).is_err());
+        assert!(select_latest(tags(), Some("stable")).is_err());
+    }
+
+    #[test]
+    fn upgrade_preserves_formatting_and_updates_default_command() {
+        let manifest = format!(
+            r#"[package]
+name = "contract"
+
+[package.metadata.near.reproducible_build]
+# docker image
+image = "sourcescan/cargo-near:0.11.0-rust-1.82.0" # pinned
+image_digest = "{DIGEST_OLD}"
+container_build_command = [
+    "cargo",
+    "near",
+    "build",
+]
+
+[package.metadata.near.reproducible_build.variant.testnet]
+container_build_command = ["cargo", "near", "build", "--features", "testnet"]
+"#
+        );
+        let tag = ImageTag::parse("0.14.1-rust-1.86.0", DIGEST_NEW).unwrap();
+        let (upgraded, changes) = upgrade_manifest(&manifest, None, &tag).unwrap();
+        assert_eq!(
+            upgraded,
+            format!(
+                r#"[package]
+name = "contract"
+
+[package.metadata.near.reproducible_build]
+# docker image
+image = "sourcescan/cargo-near:0.14.1-rust-1.86.0" # pinned
+image_digest = "{DIGEST_NEW}"
+container_build_command = [
+    "cargo",
+    "near",
+    "build",
+    "non-reproducible-wasm",
+    "--locked",
+]
+
+[package.metadata.near.reproducible_build.variant.testnet]
+container_build_command = ["cargo", "near", "build", "--features", "testnet"]
+"#
+            )
+        );
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.field)
+                .collect::<Vec<_>>(),
+            vec!["image", "image_digest", "container_build_command"]
+        );
+
+        let (_, changes) = upgrade_manifest(&upgraded, None, &tag).unwrap();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn upgrade_variant_keeps_custom_command() {
+        let manifest = format!(
+            r#"[package.metadata.near.reproducible_build]
+image = "sourcescan/cargo-near:0.11.0-rust-1.82.0"
+image_digest = "{DIGEST_OLD}"
+container_build_command = ["cargo", "near", "build"]
+
+[package.metadata.near.reproducible_build.variant.testnet]
+container_build_command = ["cargo", "near", "build", "--features", "testnet"]
+"#
+        );
+        let tag = ImageTag::parse("0.14.1-rust-1.86.0", DIGEST_NEW).unwrap();
+        let (upgraded, changes) = upgrade_manifest(&manifest, Some("testnet"), &tag).unwrap();
+        assert_eq!(
+            upgraded,
+            format!(
+                r#"[package.metadata.near.reproducible_build]
+image = "sourcescan/cargo-near:0.11.0-rust-1.82.0"
+image_digest = "{DIGEST_OLD}"
+container_build_command = ["cargo", "near", "build"]
+
+[package.metadata.near.reproducible_build.variant.testnet]
+container_build_command = ["cargo", "near", "build", "--features", "testnet"]
+image = "sourcescan/cargo-near:0.14.1-rust-1.86.0"
+image_digest = "{DIGEST_NEW}"
+"#
+            )
+        );
+        assert_eq!(changes.len(), 2);
+        assert!(upgrade_manifest(&manifest, Some("mainnet"), &tag).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use colored::Colorize;
+use eyre::{ContextCompat, WrapErr};
+use near_verify_rs::types::contract_source_metadata::{BuildInfo, ContractSourceMetadata};
+
+use crate::pretty_print;
+use crate::types::near::build::checksum::SHA256Checksum;
+use crate::types::near::docker_build::container_runtime::ContainerRuntime;
+use crate::types::near::docker_build::verify::{ExpectedHash, Opts};
+use crate::types::near::docker_build::{cloned_repo, vendor};
+
+/// `git+<url>?rev=<rev>` form of [`BuildInfo::source_code_snapshot`]
+struct SourceSnapshot {
+    url: String,
+    rev: String,
+}
+
+impl SourceSnapshot {
+    fn parse(source_code_snapshot: &str) -> eyre::Result<Self> {
+        let url = source_code_snapshot
+            .strip_prefix("git+")
+            .wrap_err_with(|| {
+                format!("`source_code_snapshot` `{source_code_snapshot}` isn't a `git+` url")
+            })?;
+        let mut url = url::Url::parse(url)
+            .wrap_err_with(|| format!("invalid `source_code_snapshot` url `{url}`"))?;
+        let rev = url
+            .query_pairs()
+            .find(|(key, _)| key == "rev")
+            .map(|(_, value)| value.into_owned())
+            .wrap_err_with(|| {
+                format!("`source_code_snapshot` `{source_code_snapshot}` has no `rev` query")
+            })?;
+        url.set_query(None);
+        url.set_fragment(None);
+        Ok(Self {
+            url: url.to_string(),
+            rev,
+        })
+    }
+}
+
+const SHA256_LEN: usize = 32;
+
+fn parse_checksum(checksum: &str) -> eyre::Result<SHA256Checksum> {
+    let hash = hex::decode(checksum)
+        .ok()
+        .filter(|hash| hash.len() == SHA256_LEN)
+        .or_else(|| {
+            bs58::decode(checksum)
+                .into_vec()
+                .ok()
+                .filter(|hash| hash.len() == SHA256_LEN)
+        })
+        .wrap_err_with(|| {
+            format!("`{checksum}` is neither a hex, nor a base58 encoded SHA-256 hash")
+        })?;
+    Ok(SHA256Checksum { hash })
+}
+
+fn read_metadata(path: &camino::Utf8Path) -> eyre::Result<ContractSourceMetadata> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("unable to read contract source metadata `{path}`"))?;
+    serde_json::from_str(&contents)
+        .wrap_err_with(|| format!("`{path}` isn't a valid NEP-330 `ContractSourceMetadata`"))
+}
+
+#[cfg(feature = "build_internal")]
+fn metadata_of_wasm(wasm_path: &camino::Utf8Path) -> eyre::Result<ContractSourceMetadata> {
+    crate::near::inspect::contract_source_metadata(wasm_path)
+}
+
+#[cfg(not(feature = "build_internal"))]
+fn metadata_of_wasm(_wasm_path: &camino::Utf8Path) -> eyre::Result<ContractSourceMetadata> {
+    Err(eyre::eyre!(
+        "extracting contract source metadata from wasm requires `build_internal` feature"
+    ))
+}
+
+/// reproduces the build, described by `build_info` of NEP-330 contract source metadata,
+/// and checks that SHA-256 of the result matches the expected one
+pub fn run(opts: Opts) -> eyre::Result<SHA256Checksum> {
+    let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
+    color.apply();
+
+    let contract_source_metadata =
+        pretty_print::handle_step("Reading contract source metadata...", || {
+            let contract_source_metadata = match (&opts.metadata, &opts.expected) {
+                (Some(path), _) => read_metadata(path)?,
+                (None, ExpectedHash::Wasm(wasm_path)) => metadata_of_wasm(wasm_path)?,
+                (None, ExpectedHash::Checksum(_)) => {
+                    return Err(eyre::eyre!(
+                        "contract source metadata is required to verify a wasm hash"
+                    ));
+                }
+            };
+            contract_source_metadata.validate(None)?;
+            Ok(contract_source_metadata)
+        })?;
+    let build_info = contract_source_metadata
+        .build_info
+        .clone()
+        .expect("checked by `ContractSourceMetadata::validate`");
+    let expected = pretty_print::handle_step("Determining expected wasm hash...", || match &opts
+        .expected
+    {
+        ExpectedHash::Wasm(path) => {
+            SHA256Checksum::new(path).wrap_err_with(|| format!("unable to read wasm `{path}`"))
+        }
+        ExpectedHash::Checksum(checksum) => parse_checksum(checksum),
+    })?;
+
+    let snapshot = SourceSnapshot::parse(&build_info.source_code_snapshot)?;
+    let (tmp_repo_dir, _tmp_repo, head) = pretty_print::handle_step(
+        &format!(
+            "Cloning `{}` at `{}` to a temporary build site...",
+            snapshot.url.magenta(),
+            snapshot.rev.magenta()
+        ),
+        || cloned_repo::clone_remote_at_revision(&snapshot.url, &snapshot.rev),
+    )?;
+
+    let workdir = camino::Utf8PathBuf::try_from(tmp_repo_dir.path().to_path_buf())?;
+    let vendored = match vendor::recorded_in_build_command(&build_info.build_command)? {
+        Some(recorded) => {
+            pretty_print::handle_step(
+                "Vendoring dependencies, as recorded in `build_command`, for a build without network access...",
+                || replay_vendoring(&workdir, &build_info, &recorded),
+            )?;
+            true
+        }
+        None => false,
+    };
+
+    let runtime = ContainerRuntime::resolve(opts.container_runtime)?;
+    super::check_docker(runtime, &build_info.build_environment, false)?;
+
+    pretty_print::step("Running build in docker command step...");
+    let out_wasm = super::container_runtime::run_build(
+        runtime,
+        &contract_source_metadata,
+        workdir,
+        super::additional_docker_args(runtime, vendored),
+        false,
+    )?;
+    let actual = SHA256Checksum::new(&out_wasm)?;
+
+    println!();
+    if actual.hash == expected.hash {
+        println!(
+            "{} reproduced wasm matches, sha256: {} (base58: {})",
+            "verified:".green().bold(),
+            actual.to_hex_string(),
+            actual.to_base58_string()
+        );
+        return Ok(actual);
+    }
+    println!("{}", "reproduced wasm doesn't match".red().bold());
+    println!(
+        "{:>14} {} (base58: {})",
+        "expected:".yellow(),
+        expected.to_hex_string(),
+        expected.to_base58_string()
+    );
+    println!(
+        "{:>14} {} (base58: {})",
+        "reproduced:".yellow(),
+        actual.to_hex_string(),
+        actual.to_base58_string()
+    );
+    println!();
+    println!(
+        "{}",
+        "inputs of the build, which determine the result:".cyan()
+    );
+    for line in explain(&build_info, &snapshot, head) {
+        println!("{}", pretty_print::indent_payload(&line));
+    }
+    Err(eyre::eyre!(
+        "SHA-256 of reproduced wasm `{}` differs from expected `{}`",
+        actual.to_hex_string(),
+        expected.to_hex_string()
+    ))
+}
+
+/// vendors dependencies of the crate at `contract_path` of the cloned repository the same way
+/// as `--vendor` does, and checks that vendored sources match the `recorded` ones
+fn replay_vendoring(
+    workdir: &camino::Utf8Path,
+    build_info: &BuildInfo,
+    recorded: &SHA256Checksum,
+) -> eyre::Result<()> {
+    let no_locked = !build_info.build_command.iter().any(|arg| arg == "--locked");
+    let vendored = vendor::vendor(&workdir.join(&build_info.contract_path), no_locked)?;
+    if vendored != *recorded {
+        return Err(eyre::eyre!(
+            "sha256 of vendored sources `{}` differs from `{}`, recorded in `build_command`",
+            vendored.to_hex_string(),
+            recorded.to_hex_string()
+        ));
+    }
+    Ok(())
+}
+
+/// per-field description of `build_info`, pointing at what each field could have caused
+/// a mismatch with
+fn explain(build_info: &BuildInfo, snapshot: &SourceSnapshot, head: git2::Oid) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "build_environment: `{}`\n  docker image, providing toolchain and system dependencies of the build",
+            build_info.build_environment
+        ),
+        format!(
+            "build_command: `{}`\n  flags of the build: features, profile, `--locked`, \
+             `--no-abi`, environment overrides, vendoring of dependencies",
+            near_verify_rs::logic::shell_escape_nep330_build_command(
+                build_info.build_command.clone()
+            )
+        ),
+        format!(
+            "contract_path: `{}`\n  crate, built within the repository{}",
+            build_info.contract_path,
+            if build_info.contract_path.is_empty() {
+                " (root of the repository)"
+            } else {
+                ""
+            }
+        ),
+    ];
+    let mut source = format!(
+        "source_code_snapshot: `{}`\n  source code and `Cargo.lock`, checked out commit {}",
+        build_info.source_code_snapshot, head
+    );
+    if head.to_string() != snapshot.rev {
+        source.push_str(&format!(
+            "\n  `{}` isn't a full commit hash, the commit it points to may differ from the one originally built",
+            snapshot.rev
+        ));
+    }
+    lines.push(source);
+    if let Some(output_wasm_path) = &build_info.output_wasm_path {
+        lines.push(format!(
+            "output_wasm_path: `{output_wasm_path}`\n  artifact, taken as the result of the build"
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMMIT: &str = "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567";
+
+    #[test]
+    fn source_snapshot_is_parsed() {
+        let snapshot =
+            SourceSnapshot::parse(&format!("git+https://github.com/org/contract?rev={COMMIT}"))
+                .unwrap();
+        assert_eq!(snapshot.url, "https://github.com/org/contract");
+        assert_eq!(snapshot.rev, COMMIT);
+
+        for (snapshot, expected) in [
+            ("git+https://github.com/org/contract", "has no `rev` query"),
+            (
+                "git+https://github.com/org/contract?branch=main",
+                "has no `rev` query",
+            ),
+            (
+                "https://github.com/org/contract?rev=0a1b2c3",
+                "isn't a `git+` url",
+            ),
+            ("git+not a url", "invalid `source_code_snapshot` url"),
+        ] {
+            let err = SourceSnapshot::parse(snapshot).err().unwrap();
+            assert!(err.to_string().contains(expected), "{snapshot}: {err}");
+        }
+    }
+
+    #[test]
+    fn checksum_is_parsed_from_hex_or_base58() {
+        let checksum = SHA256Checksum {
+            hash: (0..SHA256_LEN as u8).collect(),
+        };
+        assert_eq!(
+            parse_checksum(&checksum.to_hex_string()).unwrap().hash,
+            checksum.hash
+        );
+        assert_eq!(
+            parse_checksum(&checksum.to_base58_string()).unwrap().hash,
+            checksum.hash
+        );
+
+        let short = SHA256Checksum {
+            hash: vec![7; SHA256_LEN - 1],
+        };
+        for invalid in [
+            short.to_hex_string(),
+            short.to_base58_string(),
+            format!("{}00", checksum.to_hex_string()),
+            "not a hash".to_string(),
+        ] {
+            let err = parse_checksum(&invalid).err().unwrap();
+            assert!(
+                err.to_string()
+                    .contains("is neither a hex, nor a base58 encoded SHA-256 hash"),
+                "{invalid}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn explanation_flags_revisions_other_than_full_commit_hash() {
+        let build_info = BuildInfo {
+            build_environment: "sourcescan/cargo-near:0.13.4-rust-1.85.0@sha256:a9d8".to_string(),
+            build_command: vec![
+                "cargo".to_string(),
+                "near".to_string(),
+                "build".to_string(),
+                "non-reproducible-wasm".to_string(),
+                "--locked".to_string(),
+            ],
+            contract_path: String::new(),
+            source_code_snapshot: format!("git+https://github.com/org/contract?rev={COMMIT}"),
+            output_wasm_path: None,
+        };
+        let head = git2::Oid::from_str(COMMIT).unwrap();
+
+        let snapshot = SourceSnapshot::parse(&build_info.source_code_snapshot).unwrap();
+        let lines = explain(&build_info, &snapshot, head);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("build_environment: `sourcescan/cargo-near"));
+        assert!(lines[1].contains("cargo near build non-reproducible-wasm --locked"));
+        assert!(lines[2].contains("(root of the repository)"));
+        assert!(lines[3].contains(&format!("checked out commit {COMMIT}")));
+        assert!(!lines[3].contains("isn't a full commit hash"));
+
+        let snapshot =
+            SourceSnapshot::parse("git+https://github.com/org/contract?rev=v1.0.0").unwrap();
+        let build_info = BuildInfo {
+            contract_path: "contracts/token".to_string(),
+            output_wasm_path: Some("target/near/token.wasm".to_string()),
+            ..build_info
+        };
+        let lines = explain(&build_info, &snapshot, head);
+        assert_eq!(lines.len(), 5);
+        assert!(!lines[2].contains("(root of the repository)"));
+        assert!(lines[3].contains("`v1.0.0` isn't a full commit hash"));
+        assert!(lines[4].contains("output_wasm_path: `target/near/token.wasm`"));
+    }
+}
@@ -237,7 +237,7 @@ pub fn suggest_cargo_near_build_checks(
     }
 }
 
-const PROD_IMAGE: &str = "sourcescan/cargo-near";
+pub(crate) const PROD_IMAGE: &str = "sourcescan/cargo-near";
 const DEV_IMAGE: &str = "dj8yfo/sourcescan";
 
 fn find_cargo_near_in_docker_img_tag(
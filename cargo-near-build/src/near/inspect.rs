@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8Path;
+use eyre::{ContextCompat, WrapErr};
+use near_verify_rs::types::contract_source_metadata::ContractSourceMetadata;
+
+use crate::near::interpreter;
+
+/// NEP-330 view method, returning [`ContractSourceMetadata`] of the contract
+pub const CONTRACT_SOURCE_METADATA: &str = "contract_source_metadata";
+
+/// executes `contract_source_metadata` view method of the wasm at `wasm_path` in an embedded interpreter
+pub fn contract_source_metadata(wasm_path: &Utf8Path) -> eyre::Result<ContractSourceMetadata> {
+    let wasm =
+        std::fs::read(wasm_path).wrap_err_with(|| format!("unable to read wasm `{wasm_path}`"))?;
+    from_module(&wasm).wrap_err_with(|| {
+        format!("unable to extract NEP-330 contract source metadata from `{wasm_path}`")
+    })
+}
+
+fn from_module(wasm: &[u8]) -> eyre::Result<ContractSourceMetadata> {
+    let module = interpreter::compile(wasm)?;
+    if !module
+        .exports()
+        .any(|export| export.name() == CONTRACT_SOURCE_METADATA && export.ty().func().is_some())
+    {
+        eyre::bail!(
+            "wasm module doesn't export `{}` function, contract was built without NEP-330 support",
+            CONTRACT_SOURCE_METADATA
+        );
+    }
+    let value = call(&module, CONTRACT_SOURCE_METADATA, vec![])?;
+    serde_json::from_slice(&value).wrap_err_with(|| {
+        format!(
+            "`{}` returned invalid metadata: {}",
+            CONTRACT_SOURCE_METADATA,
+            String::from_utf8_lossy(&value)
+        )
+    })
+}
+
+/// calls exported `method` of `module` with `input` as its argument, returning the value,
+/// passed to `value_return`
+fn call(module: &wasmi::Module, method: &str, input: Vec<u8>) -> eyre::Result<Vec<u8>> {
+    let mut instance = interpreter::Instance::new(
+        module,
+        interpreter::host::Host::new(input, BTreeMap::new()),
+        interpreter::host::define,
+    )?;
+    instance.call(method, &[])?;
+    instance
+        .into_data()
+        .return_value
+        .wrap_err_with(|| format!("`{method}` didn't return a value"))
+}
+
+/// prints NEP-330 contract source metadata of the wasm at `wasm_path` as json
+pub fn run(wasm_path: &Utf8Path) -> eyre::Result<ContractSourceMetadata> {
+    let metadata = contract_source_metadata(wasm_path)?;
+    println!("{}", serde_json::to_string_pretty(&metadata)?);
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    fn leb128(mut value: u64) -> Vec<u8> {
+        let mut result = vec![];
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 && byte & 0x40 == 0 {
+                result.push(byte);
+                return result;
+            }
+            result.push(byte | 0x80);
+        }
+    }
+
+    fn section(id: u8, payload: &[u8]) -> Vec<u8> {
+        [&[id][..], &leb128(payload.len() as u64), payload].concat()
+    }
+
+    fn name(name: &str) -> Vec<u8> {
+        [leb128(name.len() as u64), name.as_bytes().to_vec()].concat()
+    }
+
+    /// module, which imports `env.value_return` and exports `contract_source_metadata`,
+    /// passing `data`, placed at offset 1024, to it
+    fn module_returning(data: &[u8]) -> Vec<u8> {
+        // i64.const, both values are small and positive, so signed and unsigned leb128 coincide
+        let i64_const = |value: u64| [&[0x42][..], &leb128(value)].concat();
+        let body = [
+            vec![0x00], // no locals
+            i64_const(data.len() as u64),
+            i64_const(1024),
+            vec![0x10, 0x00], // call 0
+            vec![0x0b],
+        ]
+        .concat();
+        let imports = [
+            vec![0x01],
+            name("env"),
+            name("value_return"),
+            vec![0x00, 0x00],
+        ]
+        .concat();
+        let exports = [
+            vec![0x02],
+            name("memory"),
+            vec![0x02, 0x00],
+            name(super::CONTRACT_SOURCE_METADATA),
+            vec![0x00, 0x01],
+        ]
+        .concat();
+        let data_segment = [
+            vec![0x01, 0x00, 0x41],
+            leb128(1024),
+            vec![0x0b],
+            leb128(data.len() as u64),
+            data.to_vec(),
+        ]
+        .concat();
+        [
+            b"\0asm\x01\0\0\0".to_vec(),
+            // (i64, i64) -> (), () -> ()
+            section(1, &[0x02, 0x60, 0x02, 0x7e, 0x7e, 0x00, 0x60, 0x00, 0x00]),
+            section(2, &imports),
+            section(3, &[0x01, 0x01]),
+            section(5, &[0x01, 0x00, 0x01]),
+            section(7, &exports),
+            section(
+                10,
+                &[&[0x01][..], &leb128(body.len() as u64), &body].concat(),
+            ),
+            section(11, &data_segment),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_contract_source_metadata() {
+        let metadata = serde_json::json!({
+            "version": "1.0.0",
+            "link": "https://github.com/org/repo",
+            "standards": [{ "standard": "nep330", "version": "1.2.0" }],
+            "build_info": {
+                "build_environment": "sourcescan/cargo-near:0.13.3-rust-1.84.0@sha256:722198ddb92d1b82cbfcd3a4a9f7fba6fd8715f4d0b5fb236d8725c4883f97de",
+                "build_command": ["cargo", "near", "build", "non-reproducible-wasm", "--locked"],
+                "contract_path": "",
+                "source_code_snapshot": "git+https://github.com/org/repo?rev=4f1c2e9a7b3d5e6f708192a3b4c5d6e7f8091a2b",
+                "output_wasm_path": null,
+            },
+        });
+        let wasm = module_returning(&serde_json::to_vec(&metadata).unwrap());
+        let extracted = super::from_module(&wasm).unwrap();
+        assert_eq!(serde_json::to_value(&extracted).unwrap(), metadata);
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        let wasm = module_returning(b"\"not metadata\"");
+        let err = super::from_module(&wasm).unwrap_err();
+        assert!(err.to_string().contains("returned invalid metadata"));
+    }
+}
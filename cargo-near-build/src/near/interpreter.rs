@@ -0,0 +1,98 @@
+//! embedded wasm interpreter ([`wasmi`]), used to evaluate exports of a contract's wasm
+//! without loading native code into `cargo-near` process and without a NEAR node
+
+use eyre::{ContextCompat, WrapErr};
+
+pub(crate) mod host;
+
+/// upper bound of instructions executed by a single instance, so that
+/// an accidental infinite loop in contract's code doesn't hang the command
+const FUEL_LIMIT: u64 = 10_000_000_000;
+
+pub(crate) struct Instance<T> {
+    store: wasmi::Store<T>,
+    instance: wasmi::Instance,
+}
+
+impl<T: 'static> Instance<T> {
+    /// instantiates `module` with host functions, defined by `define_host`
+    ///
+    /// all other imported functions are stubbed with ones, which trap with a descriptive message,
+    /// if they're called
+    pub fn new(
+        module: &wasmi::Module,
+        data: T,
+        define_host: impl FnOnce(&mut wasmi::Linker<T>) -> eyre::Result<()>,
+    ) -> eyre::Result<Self> {
+        let mut store = wasmi::Store::new(module.engine(), data);
+        store
+            .set_fuel(FUEL_LIMIT)
+            .wrap_err("interpreter engine wasn't configured to consume fuel")?;
+        let mut linker = wasmi::Linker::new(module.engine());
+        for import in module.imports() {
+            let Some(func_type) = import.ty().func() else {
+                continue;
+            };
+            let qualified_name = format!("{}.{}", import.module(), import.name());
+            linker.func_new(
+                import.module(),
+                import.name(),
+                func_type.clone(),
+                move |_caller, _params, _results| {
+                    Err(wasmi::Error::new(format!(
+                        "host function `{qualified_name}` isn't available in the embedded interpreter"
+                    )))
+                },
+            )?;
+        }
+        // `define_host` replaces stubs of the functions it provides
+        linker.allow_shadowing(true);
+        define_host(&mut linker)?;
+        let instance = linker.instantiate_and_start(&mut store, module)?;
+        Ok(Self { store, instance })
+    }
+
+    /// calls exported function `name` with `params`, returning its results
+    pub fn call(&mut self, name: &str, params: &[wasmi::Val]) -> eyre::Result<Vec<wasmi::Val>> {
+        let func = self
+            .instance
+            .get_func(&self.store, name)
+            .wrap_err_with(|| format!("wasm module doesn't export function `{name}`"))?;
+        let mut results = func
+            .ty(&self.store)
+            .results()
+            .iter()
+            .map(|ty| wasmi::Val::default(*ty))
+            .collect::<Vec<_>>();
+        func.call(&mut self.store, params, &mut results)
+            .wrap_err_with(|| format!("calling `{name}` failed"))?;
+        Ok(results)
+    }
+
+    /// host state of the instance
+    pub fn into_data(self) -> T {
+        self.store.into_data()
+    }
+}
+
+/// reads `len` bytes at `ptr` from `memory`, failing on out-of-bounds access
+pub(crate) fn read<T>(
+    memory: wasmi::Memory,
+    ctx: impl wasmi::AsContext<Data = T>,
+    ptr: u64,
+    len: u64,
+) -> eyre::Result<Vec<u8>> {
+    let mut buf = vec![0; usize::try_from(len)?];
+    memory
+        .read(&ctx, usize::try_from(ptr)?, &mut buf)
+        .map_err(|err| eyre::eyre!("out of bounds memory access at {ptr}+{len}: {err}"))?;
+    Ok(buf)
+}
+
+/// compiles `wasm` for the embedded interpreter
+pub(crate) fn compile(wasm: &[u8]) -> eyre::Result<wasmi::Module> {
+    let mut config = wasmi::Config::default();
+    config.consume_fuel(true);
+    let engine = wasmi::Engine::new(&config);
+    wasmi::Module::new(&engine, wasm).wrap_err("unable to parse wasm module")
+}
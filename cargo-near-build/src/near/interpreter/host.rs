@@ -0,0 +1,200 @@
+//! subset of NEAR host functions, sufficient for view methods, which don't depend on context
+//! of the blockchain (accounts, block, balances), e.g. `contract_source_metadata` of NEP-330
+
+use std::collections::{BTreeMap, HashMap};
+
+const ENV: &str = "env";
+
+/// state of the mocked NEAR host, available to host functions, defined by [`define`]
+#[derive(Debug, Default)]
+pub(crate) struct Host {
+    /// argument of the called method, returned by `input` host function
+    input: Vec<u8>,
+    registers: HashMap<u64, Vec<u8>>,
+    /// value, passed to `value_return` host function
+    pub return_value: Option<Vec<u8>>,
+    /// contract's state, available to `storage_read` and `storage_has_key` host functions
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// messages, passed to `log_utf8` host function
+    pub logs: Vec<String>,
+}
+
+impl Host {
+    pub fn new(input: Vec<u8>, storage: BTreeMap<Vec<u8>, Vec<u8>>) -> Self {
+        Self {
+            input,
+            storage,
+            ..Default::default()
+        }
+    }
+}
+
+fn trap(err: impl std::fmt::Display) -> wasmi::Error {
+    wasmi::Error::new(err.to_string())
+}
+
+fn memory(caller: &wasmi::Caller<'_, Host>) -> Result<wasmi::Memory, wasmi::Error> {
+    caller
+        .get_export("memory")
+        .and_then(wasmi::Extern::into_memory)
+        .ok_or_else(|| trap("wasm module doesn't export `memory`"))
+}
+
+fn read(caller: &wasmi::Caller<'_, Host>, ptr: u64, len: u64) -> Result<Vec<u8>, wasmi::Error> {
+    super::read(memory(caller)?, caller, ptr, len).map_err(trap)
+}
+
+fn set_register(caller: &mut wasmi::Caller<'_, Host>, register_id: u64, data: Vec<u8>) {
+    caller.data_mut().registers.insert(register_id, data);
+}
+
+/// defines `input`, register, `value_return`, panic, log, storage reading and `sha256`
+/// host functions in `linker`
+pub(crate) fn define(linker: &mut wasmi::Linker<Host>) -> eyre::Result<()> {
+    linker.func_wrap(
+        ENV,
+        "input",
+        |mut caller: wasmi::Caller<'_, Host>, register_id: u64| {
+            let input = caller.data().input.clone();
+            set_register(&mut caller, register_id, input);
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "register_len",
+        |caller: wasmi::Caller<'_, Host>, register_id: u64| -> u64 {
+            caller
+                .data()
+                .registers
+                .get(&register_id)
+                .map_or(u64::MAX, |data| data.len() as u64)
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "read_register",
+        |mut caller: wasmi::Caller<'_, Host>,
+         register_id: u64,
+         ptr: u64|
+         -> Result<(), wasmi::Error> {
+            let data = caller
+                .data()
+                .registers
+                .get(&register_id)
+                .cloned()
+                .ok_or_else(|| trap(format!("register {register_id} is empty")))?;
+            let memory = memory(&caller)?;
+            memory
+                .write(&mut caller, usize::try_from(ptr).map_err(trap)?, &data)
+                .map_err(trap)
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "value_return",
+        |mut caller: wasmi::Caller<'_, Host>, len: u64, ptr: u64| -> Result<(), wasmi::Error> {
+            let value = read(&caller, ptr, len)?;
+            caller.data_mut().return_value = Some(value);
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "panic",
+        |_caller: wasmi::Caller<'_, Host>| -> Result<(), wasmi::Error> {
+            Err(trap("contract panicked"))
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "panic_utf8",
+        |caller: wasmi::Caller<'_, Host>, len: u64, ptr: u64| -> Result<(), wasmi::Error> {
+            let message = read(&caller, ptr, len)?;
+            Err(trap(format!(
+                "contract panicked: {}",
+                String::from_utf8_lossy(&message)
+            )))
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "log_utf8",
+        |mut caller: wasmi::Caller<'_, Host>, len: u64, ptr: u64| -> Result<(), wasmi::Error> {
+            let message = read(&caller, ptr, len)?;
+            caller
+                .data_mut()
+                .logs
+                .push(String::from_utf8_lossy(&message).into_owned());
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "storage_read",
+        |mut caller: wasmi::Caller<'_, Host>,
+         key_len: u64,
+         key_ptr: u64,
+         register_id: u64|
+         -> Result<u64, wasmi::Error> {
+            let key = read(&caller, key_ptr, key_len)?;
+            match caller.data().storage.get(&key).cloned() {
+                Some(value) => {
+                    set_register(&mut caller, register_id, value);
+                    Ok(1)
+                }
+                None => Ok(0),
+            }
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "storage_has_key",
+        |caller: wasmi::Caller<'_, Host>,
+         key_len: u64,
+         key_ptr: u64|
+         -> Result<u64, wasmi::Error> {
+            let key = read(&caller, key_ptr, key_len)?;
+            Ok(caller.data().storage.contains_key(&key).into())
+        },
+    )?;
+    // view calls can't modify state
+    linker.func_wrap(
+        ENV,
+        "storage_write",
+        |_caller: wasmi::Caller<'_, Host>,
+         _key_len: u64,
+         _key_ptr: u64,
+         _value_len: u64,
+         _value_ptr: u64,
+         _register_id: u64|
+         -> Result<u64, wasmi::Error> {
+            Err(trap("`storage_write` isn't allowed in view calls"))
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "storage_remove",
+        |_caller: wasmi::Caller<'_, Host>,
+         _key_len: u64,
+         _key_ptr: u64,
+         _register_id: u64|
+         -> Result<u64, wasmi::Error> {
+            Err(trap("`storage_remove` isn't allowed in view calls"))
+        },
+    )?;
+    linker.func_wrap(
+        ENV,
+        "sha256",
+        |mut caller: wasmi::Caller<'_, Host>,
+         value_len: u64,
+         value_ptr: u64,
+         register_id: u64|
+         -> Result<(), wasmi::Error> {
+            let value = read(&caller, value_ptr, value_len)?;
+            let hash = <sha2::Sha256 as sha2::Digest>::digest(&value);
+            set_register(&mut caller, register_id, hash.to_vec());
+            Ok(())
+        },
+    )?;
+    Ok(())
+}
@@ -4,6 +4,12 @@ pub mod abi;
 pub mod build;
 #[cfg(feature = "build_internal")]
 pub mod check;
+#[cfg(all(feature = "build_internal", feature = "docker"))]
+pub mod inspect;
+#[cfg(feature = "build_internal")]
+pub(crate) mod interpreter;
+#[cfg(feature = "build_internal")]
+pub mod run_view;
 
 #[cfg(feature = "build_external")]
 pub mod build_external;
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use camino::Utf8Path;
+use colored::Colorize;
+use eyre::WrapErr;
+use serde::Deserialize;
+
+use crate::near::interpreter;
+use crate::types::near::run_view::{Opts, ViewOutcome};
+
+#[derive(Deserialize)]
+struct StateItem {
+    key: String,
+    value: String,
+}
+
+/// result of `view_state` RPC query, or just its `values`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StateDump {
+    Query { values: Vec<StateItem> },
+    Values(Vec<StateItem>),
+}
+
+fn parse_state(json: &str) -> eyre::Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+    let dump: StateDump = serde_json::from_str(json).wrap_err(
+        "expected `{\"values\": [{\"key\": \"<base64>\", \"value\": \"<base64>\"}, ...]}`",
+    )?;
+    let (StateDump::Query { values } | StateDump::Values(values)) = dump;
+    let decode = |field: &str, encoded: &str| {
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .wrap_err_with(|| format!("{field} `{encoded}` isn't valid base64"))
+    };
+    values
+        .into_iter()
+        .map(|item| Ok((decode("key", &item.key)?, decode("value", &item.value)?)))
+        .collect()
+}
+
+fn read_state(path: &Utf8Path) -> eyre::Result<BTreeMap<Vec<u8>, Vec<u8>>> {
+    let json = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("unable to read state dump `{path}`"))?;
+    parse_state(&json).wrap_err_with(|| format!("malformed state dump `{path}`"))
+}
+
+/// pretty-printed JSON, if `value` is JSON, or the string, if it's UTF-8, or hex otherwise
+fn display_value(value: &[u8]) -> String {
+    if let Ok(json) = serde_json::from_slice::<serde_json::Value>(value) {
+        return serde_json::to_string_pretty(&json).expect("serializing `Value` doesn't fail");
+    }
+    match std::str::from_utf8(value) {
+        Ok(string) => string.to_string(),
+        Err(_) => format!("0x{}", hex::encode(value)),
+    }
+}
+
+/// calls view `method` of `wasm` with `args` and `storage`, collecting logs even if the method panics
+fn call(
+    wasm: &[u8],
+    method: &str,
+    args: Vec<u8>,
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+) -> (ViewOutcome, eyre::Result<()>) {
+    let host = interpreter::host::Host::new(args, storage);
+    let mut instance = match interpreter::compile(wasm)
+        .and_then(|module| interpreter::Instance::new(&module, host, interpreter::host::define))
+    {
+        Ok(instance) => instance,
+        Err(err) => {
+            let outcome = ViewOutcome {
+                return_value: None,
+                logs: vec![],
+            };
+            return (outcome, Err(err));
+        }
+    };
+    let result = instance.call(method, &[]).map(|_| ());
+    let host = instance.into_data();
+    let outcome = ViewOutcome {
+        return_value: host.return_value,
+        logs: host.logs,
+    };
+    (outcome, result)
+}
+
+/// calls a view method of contract's wasm in an embedded interpreter, printing its logs and return value
+pub fn run(opts: Opts) -> eyre::Result<ViewOutcome> {
+    let color = opts.color.unwrap_or(crate::ColorPreference::Auto);
+    color.apply();
+
+    let wasm = std::fs::read(&opts.wasm)
+        .wrap_err_with(|| format!("unable to read wasm `{}`", opts.wasm))?;
+    let storage = match &opts.state {
+        Some(path) => read_state(path)?,
+        None => BTreeMap::new(),
+    };
+    let args = opts.args.unwrap_or_default().into_bytes();
+
+    let (outcome, result) = call(&wasm, &opts.method, args, storage);
+    for log in &outcome.logs {
+        eprintln!("{} {}", "log:".cyan(), log);
+    }
+    result.wrap_err_with(|| format!("view call of `{}` failed", opts.method))?;
+    match &outcome.return_value {
+        Some(value) => println!("{}", display_value(value)),
+        None => eprintln!("{}", "method didn't return a value".yellow()),
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_parse_state() {
+        let expected = BTreeMap::from([
+            (b"STATE".to_vec(), vec![1, 0, 0, 0]),
+            (b"a".to_vec(), b"hello".to_vec()),
+        ]);
+        let query = r#"{
+            "block_hash": "EGDN1n8xyUXnj1TAz3uG1b4Pa9xsnjczzCxfDbJ7XEbS",
+            "block_height": 1,
+            "values": [
+                {"key": "U1RBVEU=", "value": "AQAAAA=="},
+                {"key": "YQ==", "value": "aGVsbG8="}
+            ]
+        }"#;
+        assert_eq!(super::parse_state(query).unwrap(), expected);
+        let values = r#"[
+            {"key": "U1RBVEU=", "value": "AQAAAA=="},
+            {"key": "YQ==", "value": "aGVsbG8="}
+        ]"#;
+        assert_eq!(super::parse_state(values).unwrap(), expected);
+
+        let err = super::parse_state(r#"[{"key": "%%%", "value": ""}]"#).unwrap_err();
+        assert!(err.to_string().contains("isn't valid base64"));
+    }
+
+    #[test]
+    fn test_display_value() {
+        assert_eq!(super::display_value(b"{\"a\":1}"), "{\n  \"a\": 1\n}");
+        assert_eq!(super::display_value(b"plain"), "plain");
+        assert_eq!(super::display_value(&[0xff, 0x00]), "0xff00");
+    }
+}
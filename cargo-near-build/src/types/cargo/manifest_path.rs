@@ -45,10 +45,10 @@ impl TryFrom<Utf8PathBuf> for ManifestPath {
     fn try_from(manifest_path: Utf8PathBuf) -> Result<Self, Self::Error> {
         match manifest_path.file_name() {
             None => {
-                eyre::bail!("the manifest-path must be a path to a Cargo.toml file")
+                eyre::bail!("the manifest-path must be a path to a Cargo.toml file");
             }
             Some(file_name) if file_name != MANIFEST_FILE_NAME => {
-                eyre::bail!("the manifest-path must be a path to a Cargo.toml file")
+                eyre::bail!("the manifest-path must be a path to a Cargo.toml file");
             }
             _ => {}
         }
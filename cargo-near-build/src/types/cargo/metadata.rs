@@ -183,39 +183,64 @@ impl CrateMetadata {
     /// avoids false-positives on a workspace sibling named `near-sdk` that the contract
     /// does not depend on.
     pub fn find_package_in_graph(&self, name: &str) -> Option<&cargo_metadata::Package> {
-        let dependency_graph = self.raw_metadata.resolve.as_ref()?;
-        let root_package_id = dependency_graph.root.as_ref()?;
+        self.packages_in_graph(false)
+            .find(|pkg| pkg.name.as_str() == name)
+    }
 
-        let nodes_by_id: std::collections::HashMap<_, _> = dependency_graph
-            .nodes
-            .iter()
-            .map(|node| (&node.id, node))
-            .collect();
+    /// Directories of the root package and of its local (path or workspace) dependencies,
+    /// reachable in the resolved dependency graph, i.e. of the packages, whose sources
+    /// aren't downloaded from a registry or a git repository.
+    #[cfg(feature = "docker")]
+    pub fn local_package_dirs(&self) -> Vec<Utf8PathBuf> {
+        self.packages_in_graph(false)
+            .filter(|pkg| pkg.source.is_none())
+            .filter_map(|pkg| pkg.manifest_path.parent().map(ToOwned::to_owned))
+            .collect()
+    }
 
+    /// Packages, reachable from the root package in the resolved dependency graph,
+    /// the root package included; dev-only dependency edges are followed only with `follow_dev_edges`.
+    pub(crate) fn packages_in_graph(
+        &self,
+        follow_dev_edges: bool,
+    ) -> impl Iterator<Item = &cargo_metadata::Package> {
         let mut reachable: std::collections::HashSet<&cargo_metadata::PackageId> =
             std::collections::HashSet::new();
-        let mut queue = std::collections::VecDeque::new();
-        queue.push_back(root_package_id);
-        reachable.insert(root_package_id);
+        if let Some((dependency_graph, root_package_id)) = self
+            .raw_metadata
+            .resolve
+            .as_ref()
+            .and_then(|graph| Some((graph, graph.root.as_ref()?)))
+        {
+            let nodes_by_id: std::collections::HashMap<_, _> = dependency_graph
+                .nodes
+                .iter()
+                .map(|node| (&node.id, node))
+                .collect();
 
-        while let Some(current_id) = queue.pop_front() {
-            let Some(node) = nodes_by_id.get(current_id) else {
-                continue;
-            };
-            for dep in &node.deps {
-                // Skip dev-only edges: a `near-sdk` pulled in solely via dev-deps must
-                // not influence the build's protocol-version decision. Empty `dep_kinds`
-                // (older cargo metadata format) is treated as a normal edge.
-                let is_dev_only = !dep.dep_kinds.is_empty()
-                    && dep
-                        .dep_kinds
-                        .iter()
-                        .all(|dk| dk.kind == cargo_metadata::DependencyKind::Development);
-                if is_dev_only {
+            let mut queue = std::collections::VecDeque::new();
+            queue.push_back(root_package_id);
+            reachable.insert(root_package_id);
+
+            while let Some(current_id) = queue.pop_front() {
+                let Some(node) = nodes_by_id.get(current_id) else {
                     continue;
-                }
-                if reachable.insert(&dep.pkg) {
-                    queue.push_back(&dep.pkg);
+                };
+                for dep in &node.deps {
+                    // Skip dev-only edges: a `near-sdk` pulled in solely via dev-deps must
+                    // not influence the build's protocol-version decision. Empty `dep_kinds`
+                    // (older cargo metadata format) is treated as a normal edge.
+                    let is_dev_only = !dep.dep_kinds.is_empty()
+                        && dep
+                            .dep_kinds
+                            .iter()
+                            .all(|dk| dk.kind == cargo_metadata::DependencyKind::Development);
+                    if is_dev_only && !follow_dev_edges {
+                        continue;
+                    }
+                    if reachable.insert(&dep.pkg) {
+                        queue.push_back(&dep.pkg);
+                    }
                 }
             }
         }
@@ -223,7 +248,7 @@ impl CrateMetadata {
         self.raw_metadata
             .packages
             .iter()
-            .find(|pkg| pkg.name.as_str() == name && reachable.contains(&pkg.id))
+            .filter(move |pkg| reachable.contains(&pkg.id))
     }
 
     /// Looks up `[package.metadata.near] min_protocol_version` on the resolved `near-sdk`
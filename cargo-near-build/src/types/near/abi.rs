@@ -1,6 +1,13 @@
 use crate::types::near::build::input::ColorPreference;
 
-#[derive(Debug, Default)]
+#[cfg(feature = "docker")]
+pub mod compat;
+pub mod filter;
+pub mod lint;
+pub mod migrate;
+pub mod validate;
+
+#[derive(Debug, Default, Clone)]
 pub struct Opts {
     /// disable implicit `--locked` flag for all `cargo` commands, enabled by default
     pub no_locked: bool,
@@ -14,6 +21,10 @@ pub struct Opts {
     pub manifest_path: Option<camino::Utf8PathBuf>,
     /// Activate additional cargo features during ABI generation
     pub features: Option<String>,
+    /// Keep only functions matching these patterns in the ABI
+    pub abi_include: Vec<String>,
+    /// Remove functions matching these patterns from the ABI
+    pub abi_exclude: Vec<String>,
     /// Coloring: auto, always, never
     pub color: Option<ColorPreference>,
 }
@@ -0,0 +1,21 @@
+use colored::Colorize;
+
+/// A change of contract's public interface, which breaks existing callers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    /// name of the affected function
+    pub function: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: `{}` {}",
+            "incompatible".red(),
+            self.function,
+            self.message
+        )
+    }
+}
@@ -0,0 +1,152 @@
+use eyre::WrapErr;
+use near_abi::{AbiFunction, AbiFunctionKind, AbiFunctionModifier};
+use serde::Deserialize;
+
+use crate::types::cargo::metadata::CrateMetadata;
+
+/// Pattern, selecting functions of [`near_abi::AbiRoot`]
+///
+/// Either a glob on function name, where `*` matches any sequence of characters
+/// and `?` matches a single character, or one of the selectors:
+/// `@private`, `@payable`, `@init`, `@view`, `@call`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Name(String),
+    Private,
+    Payable,
+    Init,
+    View,
+    Call,
+}
+
+impl std::str::FromStr for Pattern {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "@private" => Ok(Self::Private),
+            "@payable" => Ok(Self::Payable),
+            "@init" => Ok(Self::Init),
+            "@view" => Ok(Self::View),
+            "@call" => Ok(Self::Call),
+            other if other.starts_with('@') => Err(eyre::eyre!(
+                "unknown ABI filter selector `{other}`, expected one of: \
+                 `@private`, `@payable`, `@init`, `@view`, `@call`"
+            )),
+            "" => Err(eyre::eyre!("ABI filter pattern can't be empty")),
+            name => Ok(Self::Name(name.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(glob) => write!(f, "{glob}"),
+            Self::Private => write!(f, "@private"),
+            Self::Payable => write!(f, "@payable"),
+            Self::Init => write!(f, "@init"),
+            Self::View => write!(f, "@view"),
+            Self::Call => write!(f, "@call"),
+        }
+    }
+}
+
+impl Pattern {
+    pub fn matches(&self, function: &AbiFunction) -> bool {
+        match self {
+            Self::Name(glob) => {
+                let glob = glob.chars().collect::<Vec<_>>();
+                let name = function.name.chars().collect::<Vec<_>>();
+                glob_matches(&glob, &name)
+            }
+            Self::Private => function.modifiers.contains(&AbiFunctionModifier::Private),
+            Self::Payable => function.modifiers.contains(&AbiFunctionModifier::Payable),
+            Self::Init => function.modifiers.contains(&AbiFunctionModifier::Init),
+            Self::View => function.kind == AbiFunctionKind::View,
+            Self::Call => function.kind == AbiFunctionKind::Call,
+        }
+    }
+}
+
+fn glob_matches(glob: &[char], name: &[char]) -> bool {
+    match glob.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        Some((&expected, rest)) => match name.split_first() {
+            Some((&actual, name)) if expected == '?' || expected == actual => {
+                glob_matches(rest, name)
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Functions, kept in generated ABI
+///
+/// If any `include` pattern is set, only functions matching at least one of them are kept;
+/// functions matching any of `exclude` patterns are then removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    pub include: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+}
+
+pub(crate) const SECTION_NAME: &str = "[package.metadata.near.abi]";
+
+#[derive(Debug, Default, Deserialize)]
+struct Section {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl Filter {
+    /// parses `include` and `exclude` patterns, e.g. ones of `--abi-include`/`--abi-exclude` flags
+    pub fn new(include: &[String], exclude: &[String]) -> eyre::Result<Self> {
+        let parse = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| pattern.parse())
+                .collect::<eyre::Result<Vec<Pattern>>>()
+        };
+        Ok(Self {
+            include: parse(include)?,
+            exclude: parse(exclude)?,
+        })
+    }
+
+    /// parses `include` and `exclude` keys of `[package.metadata.near.abi]` in Cargo.toml
+    pub fn parse(crate_metadata: &CrateMetadata) -> eyre::Result<Self> {
+        let Some(section_value) = crate_metadata
+            .root_package
+            .metadata
+            .get("near")
+            .and_then(|value| value.get("abi"))
+        else {
+            return Ok(Self::default());
+        };
+        let section: Section = serde_json::from_value(section_value.clone())
+            .wrap_err_with(|| format!("Malformed `{SECTION_NAME}` in Cargo.toml"))?;
+        Self::new(&section.include, &section.exclude)
+            .wrap_err_with(|| format!("Malformed `{SECTION_NAME}` in Cargo.toml"))
+    }
+
+    /// patterns of `self`, followed by patterns of `other`
+    pub fn merge(mut self, other: &Self) -> Self {
+        self.include.extend(other.include.iter().cloned());
+        self.exclude.extend(other.exclude.iter().cloned());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn keeps(&self, function: &AbiFunction) -> bool {
+        let included =
+            self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(function));
+        included && !self.exclude.iter().any(|pattern| pattern.matches(function))
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use eyre::WrapErr;
+use serde::Deserialize;
+
+use crate::types::cargo::metadata::CrateMetadata;
+
+/// Severity of an ABI lint, configured per rule in
+/// `[package.metadata.near.abi.lints]` section of contract's `Cargo.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// the rule is not checked
+    Allow,
+    /// findings are reported, but don't fail the command
+    Warn,
+    /// findings are reported and fail the command
+    Deny,
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warning"),
+            Self::Deny => write!(f, "error"),
+        }
+    }
+}
+
+/// Rules, checked against generated [`near_abi::AbiRoot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    /// public function has no rustdoc
+    MissingDocs,
+    /// `call` function takes a `u64`/`u128` argument as a JSON number
+    /// instead of a string wrapper (`U64`/`U128`)
+    JsonLargeIntegers,
+    /// `view` function is marked `#[payable]`
+    PayableView,
+    /// `#[init]` function is not guarded by `#[private]`
+    UnguardedInit,
+    /// schema definition is not referenced by any function
+    UnusedDefinitions,
+    /// function or argument name is not in `snake_case`
+    Naming,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 6] = [
+        Rule::MissingDocs,
+        Rule::JsonLargeIntegers,
+        Rule::PayableView,
+        Rule::UnguardedInit,
+        Rule::UnusedDefinitions,
+        Rule::Naming,
+    ];
+
+    /// key of the rule in `[package.metadata.near.abi.lints]`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::MissingDocs => "missing_docs",
+            Self::JsonLargeIntegers => "json_large_integers",
+            Self::PayableView => "payable_view",
+            Self::UnguardedInit => "unguarded_init",
+            Self::UnusedDefinitions => "unused_definitions",
+            Self::Naming => "naming",
+        }
+    }
+
+    fn default_level(&self) -> Level {
+        match self {
+            Self::PayableView => Level::Deny,
+            Self::MissingDocs
+            | Self::JsonLargeIntegers
+            | Self::UnguardedInit
+            | Self::UnusedDefinitions
+            | Self::Naming => Level::Warn,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.key() == key)
+    }
+}
+
+/// parsed from `[package.metadata.near.abi.lints]` in Cargo.toml,
+/// rules absent from the section keep their default [`Level`]
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub(crate) levels: BTreeMap<Rule, Level>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            levels: Rule::ALL
+                .into_iter()
+                .map(|rule| (rule, rule.default_level()))
+                .collect(),
+        }
+    }
+}
+
+pub(crate) const SECTION_NAME: &str = "[package.metadata.near.abi.lints]";
+
+impl Config {
+    pub fn parse(crate_metadata: &CrateMetadata) -> eyre::Result<Self> {
+        let Some(lints_value) = crate_metadata
+            .root_package
+            .metadata
+            .get("near")
+            .and_then(|value| value.get("abi"))
+            .and_then(|value| value.get("lints"))
+        else {
+            return Ok(Self::default());
+        };
+        let configured: BTreeMap<String, Level> = serde_json::from_value(lints_value.clone())
+            .wrap_err_with(|| format!("Malformed `{SECTION_NAME}` in Cargo.toml"))?;
+
+        Self::from_configured(configured)
+    }
+
+    pub(crate) fn from_configured(configured: BTreeMap<String, Level>) -> eyre::Result<Self> {
+        let mut result = Self::default();
+        let mut unknown_keys = vec![];
+        for (key, level) in configured {
+            match Rule::from_key(&key) {
+                Some(rule) => {
+                    result.levels.insert(rule, level);
+                }
+                None => unknown_keys.push(key),
+            }
+        }
+        if !unknown_keys.is_empty() {
+            return Err(eyre::eyre!(
+                "Malformed `{}` in Cargo.toml, contains unknown keys: `{}`\nknown keys: `{}`",
+                SECTION_NAME,
+                unknown_keys.join(","),
+                Rule::ALL.map(|rule| rule.key()).join(","),
+            ));
+        }
+        Ok(result)
+    }
+
+    pub fn level(&self, rule: Rule) -> Level {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or(rule.default_level())
+    }
+}
+
+/// A single diagnostic produced by a lint [`Rule`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub rule: Rule,
+    pub level: Level,
+    pub message: String,
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = format!("{}[{}]", self.level, self.rule.key());
+        let header = match self.level {
+            Level::Deny => header.red(),
+            Level::Warn | Level::Allow => header.yellow(),
+        };
+        write!(f, "{}: {}", header, self.message)
+    }
+}
@@ -0,0 +1,16 @@
+use colored::Colorize;
+
+/// A part of ABI, which couldn't be converted to the current schema version without
+/// dropping or guessing some information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LossyConversion {
+    /// location of the converted value, e.g. `ft_transfer.params` or `body.events`
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LossyConversion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: `{}` {}", "lossy".yellow(), self.path, self.message)
+    }
+}
@@ -0,0 +1,28 @@
+use colored::Colorize;
+
+/// A mismatch of function call arguments against JSON schema in contract's ABI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub severity: Severity,
+    /// location of the offending value, e.g. `$.owner_id` or `$.tokens[2].amount`
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "invalid".red(),
+            Severity::Warning => "warning".yellow(),
+        };
+        write!(f, "{}: `{}` {}", label, self.path, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// arguments would be rejected by the contract
+    Error,
+    /// arguments would be accepted, but are likely not the intended ones, e.g. with a misspelled argument
+    Warning,
+}
@@ -69,6 +69,13 @@ pub struct Opts {
     /// If not specified, `features` will be used for ABI generation.
     #[builder(into)]
     pub abi_features: Option<String>,
+    /// Keep only functions, matching these patterns, in the ABI:
+    /// globs on function name or `@private`, `@payable`, `@init`, `@view`, `@call` selectors
+    #[builder(default)]
+    pub abi_include: Vec<String>,
+    /// Remove functions, matching these patterns, from the ABI
+    #[builder(default)]
+    pub abi_exclude: Vec<String>,
     /// Disables default feature flags.
     #[builder(default)]
     pub no_default_features: bool,
@@ -185,6 +192,12 @@ impl Opts {
         if let Some(abi_features) = effective_abi_features {
             cargo_args.extend(&["--abi-features", abi_features]);
         }
+        for pattern in self.abi_include.iter() {
+            cargo_args.extend(&["--abi-include", pattern]);
+        }
+        for pattern in self.abi_exclude.iter() {
+            cargo_args.extend(&["--abi-exclude", pattern]);
+        }
         if self.no_default_features {
             cargo_args.push("--no-default-features");
         }
@@ -421,6 +434,8 @@ mod tests {
             out_dir: Some("target/out".into()),
             features: Some("feat".into()),
             abi_features: Some("abi-feat".into()),
+            abi_include: vec!["get_*".into()],
+            abi_exclude: vec!["@private".into()],
             no_default_features: true,
             color: Some(super::ColorPreference::Always),
             env: vec![("K".into(), "V".into())],
@@ -441,6 +456,8 @@ mod tests {
         assert!(has_flag_with_value(&cmd, "--out-dir", "target/out"));
         assert!(has_flag_with_value(&cmd, "--features", "feat"));
         assert!(has_flag_with_value(&cmd, "--abi-features", "abi-feat"));
+        assert!(has_flag_with_value(&cmd, "--abi-include", "get_*"));
+        assert!(has_flag_with_value(&cmd, "--abi-exclude", "@private"));
         assert!(cmd.contains(&"--no-default-features".to_string()));
         assert!(has_flag_with_value(&cmd, "--color", "always"));
         assert!(has_flag_with_value(&cmd, "--env", "K=V"));
@@ -13,6 +13,7 @@ pub mod checksum {
     use sha2::{Digest, Sha256};
 
     /// convenience helper to compute resulting artifact hashsum if needed
+    #[derive(Clone, Debug, PartialEq, Eq)]
     pub struct SHA256Checksum {
         pub hash: Vec<u8>,
     }
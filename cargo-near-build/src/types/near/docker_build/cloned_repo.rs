@@ -8,7 +8,7 @@ use crate::types::cargo::metadata::CrateMetadata;
 use crate::types::near::build::common_buildtime_env::CargoTargetDir;
 use crate::types::near::build::output::version_info::VersionInfo;
 use crate::types::near::build::side_effects::ArtifactMessages;
-use crate::types::near::docker_build::WARN_BECOMES_ERR;
+use crate::types::near::docker_build::{RemoteSource, WARN_BECOMES_ERR};
 use crate::{BuildArtifact, camino};
 use colored::Colorize;
 
@@ -22,38 +22,85 @@ pub struct ClonedRepo {
     pub tmp_repo_dir: tempfile::TempDir,
     no_locked: bool,
     tmp_crate_metadata: CrateMetadata,
+    /// repo was cloned from a remote url, so there's no local crate to copy the artifact to
+    from_remote: bool,
 }
 
 impl ClonedRepo {
+    /// clones repo, containing `crate_in_repo`, at `revision`, or at current HEAD, if it's `None`
     pub fn check_locked_then_clone(
         crate_in_repo: crate_in_repo::Crate,
         no_locked: bool,
         context: BuildContext,
+        revision: Option<&str>,
     ) -> eyre::Result<Self> {
-        match (no_locked, context) {
-            (false, _) => {}
-            (true, BuildContext::Build) => {
-                no_locked_warn_pause(true);
-                println!();
-                println!("{}", WARN_BECOMES_ERR.red(),);
-                std::thread::sleep(Duration::new(5, 0));
-            }
-            (true, BuildContext::Deploy { .. }) => {
-                println!(
-                    "{}",
-                    "Check in Cargo.lock for contract being built into source control.".yellow()
-                );
-                return Err(eyre::eyre!(ERR_NO_LOCKED_DEPLOY));
-            }
+        check_locked(no_locked, context)?;
+        Self::git_clone(crate_in_repo, no_locked, revision)
+    }
+
+    /// clones `remote` repository and checks out its revision, the contract's crate
+    /// is at `remote.contract_path` of the clone
+    pub fn check_locked_then_clone_remote(
+        remote: &RemoteSource,
+        no_locked: bool,
+        context: BuildContext,
+    ) -> eyre::Result<Self> {
+        check_locked(no_locked, context)?;
+        if !remote.contract_path.components().all(|component| {
+            matches!(
+                component,
+                camino::Utf8Component::Normal(_) | camino::Utf8Component::CurDir
+            )
+        }) {
+            return Err(eyre::eyre!(
+                "contract path `{}` is expected to be relative to the root of the repository, without `..`",
+                remote.contract_path
+            ));
         }
-        Self::git_clone(crate_in_repo, no_locked)
+        let (tmp_repo_dir, tmp_repo, head) = clone_remote_at_revision(&remote.repo, &remote.rev)?;
+        let repo_root: camino::Utf8PathBuf = tmp_repo_dir.path().to_path_buf().try_into()?;
+        let crate_in_repo = crate_in_repo::Crate {
+            crate_root: repo_root.join(&remote.contract_path),
+            repo_root,
+            head,
+        };
+        Self::collect_from_clone(tmp_repo_dir, &tmp_repo, crate_in_repo, no_locked, true)
     }
 
-    fn git_clone(crate_in_repo: crate_in_repo::Crate, no_locked: bool) -> eyre::Result<Self> {
+    /// clones repo, containing `crate_in_repo`, and checks out `revision`
+    /// (a commit, tag or branch name) instead of current HEAD
+    pub fn clone_at_revision(
+        crate_in_repo: crate_in_repo::Crate,
+        no_locked: bool,
+        revision: &str,
+    ) -> eyre::Result<Self> {
+        Self::git_clone(crate_in_repo, no_locked, Some(revision))
+    }
+
+    /// [`crate_in_repo::Crate::head`] of resulting [`ClonedRepo::initial_crate_in_repo`]
+    /// is the checked out `revision`, if it's set
+    fn git_clone(
+        mut crate_in_repo: crate_in_repo::Crate,
+        no_locked: bool,
+        revision: Option<&str>,
+    ) -> eyre::Result<Self> {
         let tmp_repo_dir = tempfile::tempdir()?;
-        let tmp_repo_path = tmp_repo_dir.path().to_path_buf();
         let tmp_repo =
-            git2::Repository::clone_recurse(crate_in_repo.repo_root.as_str(), &tmp_repo_path)?;
+            git2::Repository::clone_recurse(crate_in_repo.repo_root.as_str(), tmp_repo_dir.path())?;
+        if let Some(revision) = revision {
+            crate_in_repo.head = checkout(&tmp_repo, revision)?;
+        }
+        Self::collect_from_clone(tmp_repo_dir, &tmp_repo, crate_in_repo, no_locked, false)
+    }
+
+    fn collect_from_clone(
+        tmp_repo_dir: tempfile::TempDir,
+        tmp_repo: &git2::Repository,
+        crate_in_repo: crate_in_repo::Crate,
+        no_locked: bool,
+        from_remote: bool,
+    ) -> eyre::Result<Self> {
+        let tmp_repo_path = tmp_repo_dir.path().to_path_buf();
         println!(
             "{} {:?}",
             format!("current HEAD ({}):", tmp_repo.path().display()).green(),
@@ -88,21 +135,52 @@ impl ClonedRepo {
             no_locked,
             initial_crate_in_repo: crate_in_repo,
             tmp_crate_metadata,
+            from_remote,
         })
     }
 
     pub fn crate_metadata(&self) -> &CrateMetadata {
         &self.tmp_crate_metadata
     }
+    /// vendors dependencies of the crate on the temporary build site,
+    /// see [`vendor::vendor`](super::vendor::vendor)
+    pub fn vendor(&self) -> eyre::Result<crate::types::near::build::checksum::SHA256Checksum> {
+        super::vendor::vendor(
+            self.tmp_crate_metadata.manifest_path.directory()?,
+            self.no_locked,
+        )
+    }
     pub fn contract_source_workdir(&self) -> eyre::Result<camino::Utf8PathBuf> {
         let path = camino::Utf8PathBuf::try_from(self.tmp_repo_dir.path().to_path_buf())?;
         Ok(path)
     }
+    /// removes target directory of the temporary build site, left by a previous build;
+    /// a target directory outside of the build site, e.g. one set by `CARGO_TARGET_DIR`, is kept
+    pub fn clean_target_dir(&self) -> eyre::Result<()> {
+        let target_dir = &self.tmp_crate_metadata.target_directory;
+        let build_site = std::fs::canonicalize(self.tmp_repo_dir.path())?;
+        if target_dir.starts_with(build_site) && target_dir.exists() {
+            std::fs::remove_dir_all(target_dir)?;
+        }
+        Ok(())
+    }
+    /// artifact of a `variant` is renamed to `<contract>.<variant>.wasm`
     pub fn copy_artifact(
-        self,
+        &self,
         in_wasm_path: camino::Utf8PathBuf,
         cli_override: Option<camino::Utf8PathBuf>,
+        variant: Option<&str>,
     ) -> eyre::Result<BuildArtifact> {
+        if self.from_remote {
+            let destination_dir = match cli_override {
+                Some(out_dir) => {
+                    std::fs::create_dir_all(&out_dir)?;
+                    out_dir
+                }
+                None => camino::Utf8PathBuf::try_from(std::env::current_dir()?)?,
+            };
+            return copy(in_wasm_path, destination_dir, variant);
+        }
         let destination_crate_metadata = {
             let cargo_toml_path: camino::Utf8PathBuf = {
                 let mut path = self.initial_crate_in_repo.crate_root.clone();
@@ -117,17 +195,76 @@ impl ClonedRepo {
             .get_legacy_cargo_near_output_path(cli_override)?
             .out_dir;
 
-        copy(in_wasm_path, destination_dir)
+        copy(in_wasm_path, destination_dir, variant)
+    }
+}
+
+/// clones repository at `url` into a temporary directory and checks out `revision` there
+pub(crate) fn clone_remote_at_revision(
+    url: &str,
+    revision: &str,
+) -> eyre::Result<(tempfile::TempDir, git2::Repository, git2::Oid)> {
+    let tmp_repo_dir = tempfile::tempdir()?;
+    let tmp_repo = git2::Repository::clone_recurse(url, tmp_repo_dir.path())
+        .map_err(|err| eyre::eyre!("unable to clone `{}`: {}", url, err))?;
+    let head = checkout(&tmp_repo, revision)?;
+    Ok((tmp_repo_dir, tmp_repo, head))
+}
+
+/// checks out `revision` in detached HEAD state, updating submodules to match it
+///
+/// branches of the original repo are only present as `origin/<branch>` in a clone,
+/// so that form is tried too
+fn checkout(repo: &git2::Repository, revision: &str) -> eyre::Result<git2::Oid> {
+    let object = repo
+        .revparse_single(revision)
+        .or_else(|_| repo.revparse_single(&format!("origin/{revision}")))
+        .map_err(|err| eyre::eyre!("git revision `{}` not found: {}", revision, err))?;
+    let commit = object.peel_to_commit()?;
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().force()),
+    )?;
+    repo.set_head_detached(commit.id())?;
+    for mut submodule in repo.submodules()? {
+        submodule.update(true, None)?;
+    }
+    Ok(commit.id())
+}
+
+fn check_locked(no_locked: bool, context: BuildContext) -> eyre::Result<()> {
+    match (no_locked, context) {
+        (false, _) => {}
+        (true, BuildContext::Build) => {
+            no_locked_warn_pause(true);
+            println!();
+            println!("{}", WARN_BECOMES_ERR.red(),);
+            std::thread::sleep(Duration::new(5, 0));
+        }
+        (true, BuildContext::Deploy { .. }) => {
+            println!(
+                "{}",
+                "Check in Cargo.lock for contract being built into source control.".yellow()
+            );
+            return Err(eyre::eyre!(ERR_NO_LOCKED_DEPLOY));
+        }
     }
+    Ok(())
 }
 
 fn copy(
     in_wasm_path: camino::Utf8PathBuf,
     mut destination_dir: camino::Utf8PathBuf,
+    variant: Option<&str>,
 ) -> eyre::Result<BuildArtifact> {
     let file_name = in_wasm_path
         .file_name()
-        .expect("expected to be a wasm file path name as the result of [near_verify_rs::logic::nep330_build::run]");
+        .expect("expected to be a wasm file path name as the result of a container build");
+    let file_name = match (variant, in_wasm_path.file_stem(), in_wasm_path.extension()) {
+        (Some(variant), Some(stem), Some(extension)) => format!("{stem}.{variant}.{extension}"),
+        (Some(variant), ..) => format!("{file_name}.{variant}"),
+        (None, ..) => file_name.to_string(),
+    };
     let out_wasm_path = {
         destination_dir.push(file_name);
         destination_dir
@@ -166,3 +303,250 @@ fn no_locked_warn_pause(warning_red: bool) {
     println!("{}", "  - such builds are not reproducible due to potential update of dependencies and compiled `wasm` mismatch as the result.".yellow());
     std::thread::sleep(Duration::new(12, 0));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_TOML: &str = r#"[package]
+name = "contract"
+version = "0.1.0"
+edition = "2021"
+repository = "https://github.com/org/contract"
+
+[package.metadata.near.reproducible_build]
+image = "sourcescan/cargo-near:0.13.4-rust-1.85.0"
+image_digest = "sha256:a9d8bee7b134856cc8baa142494a177f2ba9ecfededfcdd38f634e14cca8aae2"
+container_build_command = ["cargo", "near", "build", "non-reproducible-wasm", "--locked"]
+"#;
+
+    const CARGO_LOCK: &str = r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 4
+
+[[package]]
+name = "contract"
+version = "0.1.0"
+"#;
+
+    /// writes the contract's crate at `contract/` of `repo` with `lib_rs` and commits it
+    fn commit_contract(repo: &git2::Repository, lib_rs: &str) -> git2::Oid {
+        let workdir = repo.workdir().unwrap();
+        std::fs::create_dir_all(workdir.join("contract/src")).unwrap();
+        for (path, contents) in [
+            ("contract/Cargo.toml", CARGO_TOML),
+            ("contract/Cargo.lock", CARGO_LOCK),
+            ("contract/src/lib.rs", lib_rs),
+        ] {
+            std::fs::write(workdir.join(path), contents).unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["contract"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        let parent = repo.head().ok().map(|head| head.peel_to_commit().unwrap());
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            lib_rs,
+            &tree,
+            parent.iter().collect::<Vec<_>>().as_slice(),
+        )
+        .unwrap()
+    }
+
+    fn remote_source(repo: &str, contract_path: &str) -> RemoteSource {
+        RemoteSource {
+            repo: repo.to_string(),
+            rev: "HEAD".to_string(),
+            contract_path: contract_path.into(),
+        }
+    }
+
+    #[test]
+    fn remote_contract_path_outside_of_repository_is_rejected() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let repo = tmp_dir.path().join("missing.git");
+        for contract_path in ["../contract", "contract/../../other", "/contract"] {
+            let err = ClonedRepo::check_locked_then_clone_remote(
+                &remote_source(repo.to_str().unwrap(), contract_path),
+                false,
+                BuildContext::Build,
+            )
+            .err()
+            .unwrap();
+            assert!(
+                err.to_string()
+                    .contains("is expected to be relative to the root of the repository"),
+                "{contract_path}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn remote_repository_is_cloned_at_revision() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let remote_path = tmp_dir.path().join("remote.git");
+        git2::Repository::init_bare(&remote_path).unwrap();
+        let local = git2::Repository::init(tmp_dir.path().join("local")).unwrap();
+        let first = commit_contract(&local, "// first\n");
+        commit_contract(&local, "// second\n");
+        let branch = local.head().unwrap().name().unwrap().to_string();
+        local
+            .remote_anonymous(remote_path.to_str().unwrap())
+            .unwrap()
+            .push(&[format!("{branch}:{branch}")], None)
+            .unwrap();
+
+        let remote = RemoteSource {
+            rev: first.to_string(),
+            ..remote_source(remote_path.to_str().unwrap(), "contract")
+        };
+        let cloned_repo =
+            ClonedRepo::check_locked_then_clone_remote(&remote, false, BuildContext::Build)
+                .unwrap();
+
+        assert!(cloned_repo.from_remote);
+        assert_eq!(cloned_repo.initial_crate_in_repo.head, first);
+        assert_eq!(
+            cloned_repo.crate_metadata().root_package.name.as_str(),
+            "contract"
+        );
+        let lib_rs = cloned_repo
+            .contract_source_workdir()
+            .unwrap()
+            .join("contract/src/lib.rs");
+        assert_eq!(std::fs::read_to_string(lib_rs).unwrap(), "// first\n");
+    }
+
+    #[test]
+    fn git_ref_is_checked_out_and_recorded_in_source_code_snapshot() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let repo_root: camino::Utf8PathBuf = tmp_dir.path().join("repo").try_into().unwrap();
+        let repo = git2::Repository::init(&repo_root).unwrap();
+        let first = commit_contract(&repo, "// first\n");
+        repo.tag_lightweight("v1", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        let second = commit_contract(&repo, "// second\n");
+
+        for git_ref in [first.to_string(), "v1".to_string()] {
+            let crate_in_repo = crate_in_repo::Crate {
+                crate_root: repo_root.join("contract"),
+                repo_root: repo_root.clone(),
+                head: second,
+            };
+            let cloned_repo =
+                ClonedRepo::clone_at_revision(crate_in_repo, false, &git_ref).unwrap();
+            assert_eq!(cloned_repo.initial_crate_in_repo.head, first, "{git_ref}");
+            let lib_rs = cloned_repo
+                .contract_source_workdir()
+                .unwrap()
+                .join("contract/src/lib.rs");
+            assert_eq!(std::fs::read_to_string(lib_rs).unwrap(), "// first\n");
+
+            let applied_build_meta = super::super::metadata::parse::ReproducibleBuild::parse(
+                cloned_repo.crate_metadata(),
+            )
+            .unwrap()
+            .apply_variant_or_default(None)
+            .unwrap();
+            let build_info = super::super::subprocess::nep330_build_info::BuildInfoMixed::new(
+                &Default::default(),
+                &applied_build_meta,
+                &cloned_repo,
+                None,
+            )
+            .unwrap();
+            assert_eq!(
+                build_info.source_code_snapshot.as_url().to_string(),
+                format!("git+https://github.com/org/contract?rev={first}")
+            );
+        }
+    }
+
+    /// clone of a repo with a committed contract at `contract/`
+    fn cloned_contract(tmp_dir: &std::path::Path) -> ClonedRepo {
+        let repo_root: camino::Utf8PathBuf = tmp_dir.join("repo").try_into().unwrap();
+        let repo = git2::Repository::init(&repo_root).unwrap();
+        let head = commit_contract(&repo, "// contract\n");
+        let crate_in_repo = crate_in_repo::Crate {
+            crate_root: repo_root.join("contract"),
+            repo_root,
+            head,
+        };
+        ClonedRepo::check_locked_then_clone(crate_in_repo, false, BuildContext::Build, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn target_dir_is_cleaned_only_inside_of_build_site() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut cloned_repo = cloned_contract(tmp_dir.path());
+
+        let target_dir = cloned_repo.tmp_crate_metadata.target_directory.clone();
+        std::fs::write(target_dir.join("contract.wasm"), b"\0asm").unwrap();
+        cloned_repo.clean_target_dir().unwrap();
+        assert!(!target_dir.exists());
+        // nothing to clean, when there was no previous build
+        cloned_repo.clean_target_dir().unwrap();
+
+        let build_site = cloned_repo.contract_source_workdir().unwrap();
+        let outside_dirs: Vec<camino::Utf8PathBuf> = vec![
+            // e.g. `CARGO_TARGET_DIR`
+            tmp_dir.path().join("shared-target").try_into().unwrap(),
+            // shares the prefix of build site's path, but isn't inside of it
+            format!("{build_site}-target").into(),
+        ];
+        for outside_dir in outside_dirs {
+            std::fs::create_dir_all(&outside_dir).unwrap();
+            std::fs::write(outside_dir.join("contract.wasm"), b"\0asm").unwrap();
+            let outside_dir = crate::fs::force_canonicalize_dir(&outside_dir).unwrap();
+            cloned_repo.tmp_crate_metadata.target_directory = outside_dir.clone();
+
+            cloned_repo.clean_target_dir().unwrap();
+            assert!(outside_dir.join("contract.wasm").exists(), "{outside_dir}");
+            std::fs::remove_dir_all(&outside_dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn artifact_of_variant_is_renamed() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let tmp_path: camino::Utf8PathBuf = tmp_dir.path().to_path_buf().try_into().unwrap();
+        let (in_dir, out_dir) = (tmp_path.join("in"), tmp_path.join("out"));
+        std::fs::create_dir_all(&in_dir).unwrap();
+        std::fs::create_dir_all(&out_dir).unwrap();
+
+        for (in_file_name, variant, out_file_name) in [
+            ("contract.wasm", None, "contract.wasm"),
+            ("contract.wasm", Some("testnet"), "contract.testnet.wasm"),
+            (
+                "my_contract.wasm",
+                Some("no-logs"),
+                "my_contract.no-logs.wasm",
+            ),
+            // no extension to insert the variant before
+            ("contract", Some("testnet"), "contract.testnet"),
+            (".wasm", Some("testnet"), ".wasm.testnet"),
+        ] {
+            let in_wasm_path = in_dir.join(in_file_name);
+            std::fs::write(&in_wasm_path, in_file_name).unwrap();
+            let artifact = copy(in_wasm_path, out_dir.clone(), variant).unwrap();
+            assert_eq!(artifact.path, out_dir.join(out_file_name), "{variant:?}");
+            assert_eq!(
+                std::fs::read_to_string(&artifact.path).unwrap(),
+                in_file_name
+            );
+        }
+
+        // artifact of a previous build is replaced
+        let in_wasm_path = in_dir.join("contract.wasm");
+        std::fs::write(&in_wasm_path, "rebuilt").unwrap();
+        let artifact = copy(in_wasm_path, out_dir.clone(), Some("testnet")).unwrap();
+        assert_eq!(std::fs::read_to_string(artifact.path).unwrap(), "rebuilt");
+    }
+}
@@ -0,0 +1,149 @@
+use crate::env_keys;
+
+/// CLI of the container engine, used for reproducible builds
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    /// rootless podman is supported: host user is mapped into the container with `--userns=keep-id`;
+    /// mounted source code is relabeled for SELinux with `:z` volume option, as with other runtimes
+    Podman,
+    Nerdctl,
+}
+
+impl ContainerRuntime {
+    pub fn binary(self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+            Self::Nerdctl => "nerdctl",
+        }
+    }
+
+    /// `runtime`, if it's set, otherwise the one of [`env_keys::CARGO_NEAR_CONTAINER_RUNTIME`],
+    /// otherwise docker
+    pub fn resolve(runtime: Option<Self>) -> eyre::Result<Self> {
+        Self::resolve_with_env(
+            runtime,
+            std::env::var(env_keys::CARGO_NEAR_CONTAINER_RUNTIME),
+        )
+    }
+
+    fn resolve_with_env(
+        runtime: Option<Self>,
+        env_value: Result<String, std::env::VarError>,
+    ) -> eyre::Result<Self> {
+        if let Some(runtime) = runtime {
+            return Ok(runtime);
+        }
+        match env_value {
+            Ok(value) => value.parse().map_err(|err| {
+                eyre::eyre!(
+                    "invalid `{}` environment variable: {}",
+                    env_keys::CARGO_NEAR_CONTAINER_RUNTIME,
+                    err
+                )
+            }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// arguments of `run` command, which the runtime needs in addition to ones passed to docker
+    pub(crate) fn additional_run_args(self) -> Vec<String> {
+        match self {
+            Self::Docker | Self::Nerdctl => vec![],
+            // `-u <uid>:<gid>` is passed to the container, so that the artifacts in mounted
+            // source code are owned by host user; in rootless podman that only holds
+            // with host user mapped to the same id in container's user namespace
+            Self::Podman => vec!["--userns=keep-id".to_string()],
+        }
+    }
+}
+
+impl std::str::FromStr for ContainerRuntime {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "docker" => Ok(Self::Docker),
+            "podman" => Ok(Self::Podman),
+            "nerdctl" => Ok(Self::Nerdctl),
+            other => Err(eyre::eyre!(
+                "unknown container runtime `{other}`, expected one of: `docker`, `podman`, `nerdctl`"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.binary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [ContainerRuntime; 3] = [
+        ContainerRuntime::Docker,
+        ContainerRuntime::Podman,
+        ContainerRuntime::Nerdctl,
+    ];
+
+    #[test]
+    fn runtime_is_parsed_from_its_binary_name() {
+        for runtime in ALL {
+            assert_eq!(
+                runtime.to_string().parse::<ContainerRuntime>().unwrap(),
+                runtime
+            );
+        }
+        assert!("Docker".parse::<ContainerRuntime>().is_err());
+        assert!("containerd".parse::<ContainerRuntime>().is_err());
+    }
+
+    #[test]
+    fn explicit_runtime_takes_precedence_over_env() {
+        assert_eq!(
+            ContainerRuntime::resolve_with_env(
+                Some(ContainerRuntime::Nerdctl),
+                Ok("podman".into())
+            )
+            .unwrap(),
+            ContainerRuntime::Nerdctl
+        );
+        assert_eq!(
+            ContainerRuntime::resolve_with_env(
+                Some(ContainerRuntime::Docker),
+                Ok("invalid".into())
+            )
+            .unwrap(),
+            ContainerRuntime::Docker
+        );
+        assert_eq!(
+            ContainerRuntime::resolve_with_env(None, Ok("podman".into())).unwrap(),
+            ContainerRuntime::Podman
+        );
+        assert_eq!(
+            ContainerRuntime::resolve_with_env(None, Err(std::env::VarError::NotPresent)).unwrap(),
+            ContainerRuntime::Docker
+        );
+        let err = ContainerRuntime::resolve_with_env(None, Ok("containerd".into())).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(env_keys::CARGO_NEAR_CONTAINER_RUNTIME),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn only_podman_needs_additional_run_args() {
+        assert!(ContainerRuntime::Docker.additional_run_args().is_empty());
+        assert!(ContainerRuntime::Nerdctl.additional_run_args().is_empty());
+        assert_eq!(
+            ContainerRuntime::Podman.additional_run_args(),
+            ["--userns=keep-id"]
+        );
+    }
+}
@@ -4,6 +4,7 @@ use serde_json::Value;
 use std::{collections::BTreeMap, thread, time::Duration};
 
 pub(crate) mod parse;
+pub mod repository;
 mod validate;
 
 pub struct AppliedReproducibleBuild {
@@ -12,8 +13,8 @@ pub struct AppliedReproducibleBuild {
     pub passed_env: Option<Vec<String>>,
     pub container_build_command: Option<Vec<String>>,
 
-    /// a cloneable git remote url,
-    /// currently, only ones, starting with `https://`, are supported;
+    /// a cloneable git remote url, either `https://` or `ssh://` one,
+    /// scp-like `git@host:org/repo.git` form is converted to the latter;
     /// parsed from `package.repository`
     pub repository: Option<url::Url>,
 
@@ -21,6 +22,9 @@ pub struct AppliedReproducibleBuild {
     /// present if the variant of build was used
     pub selected_variant: Option<String>,
 
+    /// variants, which `selected_variant` extends, from the nearest to the most distant one
+    pub extended_variants: Vec<String>,
+
     unknown_keys: BTreeMap<String, Value>,
 }
 
@@ -38,7 +42,17 @@ impl std::fmt::Display for AppliedReproducibleBuild {
         writeln!(f)?;
 
         if let Some(ref variant_name) = self.selected_variant {
-            writeln!(f, "    {}: {}", "build variant", variant_name.yellow())?;
+            if self.extended_variants.is_empty() {
+                writeln!(f, "    {}: {}", "build variant", variant_name.yellow())?;
+            } else {
+                writeln!(
+                    f,
+                    "    {}: {} (extends: {})",
+                    "build variant",
+                    variant_name.yellow(),
+                    self.extended_variants.join(" -> ")
+                )?;
+            }
         } else {
             writeln!(f, "    {}: {}", "build variant", "<DEFAULT>")?;
         }
@@ -78,6 +92,16 @@ impl std::fmt::Display for AppliedReproducibleBuild {
                 .map(|url| url.as_ref())
                 .unwrap_or("<empty>")
         )?;
+        if let Some(https_repository) = self
+            .https_repository()
+            .filter(|https_repository| Some(https_repository) != self.repository.as_ref())
+        {
+            writeln!(
+                f,
+                "    {}: {}",
+                "repository in NEP-330 metadata", https_repository
+            )?;
+        }
         Ok(())
     }
 }
@@ -91,84 +115,105 @@ impl AppliedReproducibleBuild {
             container_build_command: reproducible_build_parsed.container_build_command.clone(),
             repository: reproducible_build_parsed.repository.clone(),
             selected_variant: None,
+            extended_variants: vec![],
             unknown_keys: reproducible_build_parsed.unknown_keys.clone(),
         }
     }
 
+    /// overlays `variant_build` onto current state, printing overridden values if `verbose`
     fn inject_variant_build(
         &mut self,
         variant_name: &str,
         variant_build: &parse::VariantReproducibleBuild,
+        verbose: bool,
     ) {
-        println!();
-        println!(
-            "{}{}{}",
-            "Injecting variant build `.variant.".yellow(),
-            variant_name.yellow(),
-            "`:".yellow()
-        );
+        if verbose {
+            println!();
+            println!(
+                "{}{}{}",
+                "Injecting variant build `.variant.".yellow(),
+                variant_name.yellow(),
+                "`:".yellow()
+            );
+        }
 
         self.selected_variant = Some(variant_name.to_string());
 
         if let Some(new_image) = &variant_build.image {
-            println!("    {}", "Changing image:".yellow());
-            println!("        {} `{}`", "default:".red(), self.image);
-            println!("        {} `{}`", "override:".green(), new_image);
-            println!();
+            if verbose {
+                println!("    {}", "Changing image:".yellow());
+                println!("        {} `{}`", "default:".red(), self.image);
+                println!("        {} `{}`", "override:".green(), new_image);
+                println!();
+            }
 
             self.image.clone_from(new_image);
         }
 
         if let Some(new_image_digest) = &variant_build.image_digest {
-            println!("    {}", "Changing image_digest:".yellow());
-            println!("        {} `{}`", "default:".red(), self.image_digest);
-            println!("        {} `{}`", "override:".green(), new_image_digest);
-            println!();
+            if verbose {
+                println!("    {}", "Changing image_digest:".yellow());
+                println!("        {} `{}`", "default:".red(), self.image_digest);
+                println!("        {} `{}`", "override:".green(), new_image_digest);
+                println!();
+            }
 
             self.image_digest.clone_from(new_image_digest);
         }
 
         if let Some(new_passed_env) = &variant_build.passed_env {
-            println!("    {}", "Changing passed_env:".yellow());
+            if verbose {
+                println!("    {}", "Changing passed_env:".yellow());
 
-            if let Some(original_passed_env) = &self.passed_env {
-                println!("        {} `{:?}`", "default:".red(), original_passed_env);
-            } else {
-                println!("        {} `{}`", "default:".red(), "<ABSENT>".green());
-            }
+                if let Some(original_passed_env) = &self.passed_env {
+                    println!("        {} `{:?}`", "default:".red(), original_passed_env);
+                } else {
+                    println!("        {} `{}`", "default:".red(), "<ABSENT>".green());
+                }
 
-            println!("        {} `{:?}`", "override:".green(), new_passed_env);
-            println!();
+                println!("        {} `{:?}`", "override:".green(), new_passed_env);
+                println!();
+            }
 
             self.passed_env = Some(new_passed_env.clone());
         }
 
         if let Some(new_container_command) = &variant_build.container_build_command {
-            println!("    {}", "Changing container_build_command:".yellow());
+            if verbose {
+                println!("    {}", "Changing container_build_command:".yellow());
+
+                if let Some(original_build_command) = &self.container_build_command {
+                    println!(
+                        "        {} `{:?}`",
+                        "default:".red(),
+                        original_build_command
+                    );
+                } else {
+                    println!("        {} `{}`", "default:".red(), "<ABSENT>".yellow());
+                }
 
-            if let Some(original_build_command) = &self.container_build_command {
                 println!(
                     "        {} `{:?}`",
-                    "default:".red(),
-                    original_build_command
+                    "override:".green(),
+                    new_container_command
                 );
-            } else {
-                println!("        {} `{}`", "default:".red(), "<ABSENT>".yellow());
+                println!();
             }
 
-            println!(
-                "        {} `{:?}`",
-                "override:".green(),
-                new_container_command
-            );
-            println!();
-
             self.container_build_command = Some(new_container_command.clone());
         }
 
         self.unknown_keys.extend(variant_build.unknown_keys.clone());
     }
 
+    /// [`Self::repository`] in canonical `https` form, which is recorded in NEP-330 metadata,
+    /// see [`repository::to_https`]
+    pub fn https_repository(&self) -> Option<url::Url> {
+        self.repository
+            .as_ref()
+            .and_then(|repository| repository::to_https(repository).ok())
+    }
+
     pub fn concat_image(&self) -> String {
         let mut result = String::new();
         result.push_str(&self.image);
@@ -181,15 +226,14 @@ impl AppliedReproducibleBuild {
 impl parse::ReproducibleBuild {
     /// Apply the variant of `[package.metadata.near.reproducible_build]` using the `variant_name`;
     /// if `variant_name` is empty - return default `[package.metadata.near.reproducible_build]`
+    ///
+    /// variants, which `variant_name` extends, are applied first, starting with the most distant one
     pub fn apply_variant_or_default(
         self,
         variant_name: Option<&str>,
     ) -> eyre::Result<AppliedReproducibleBuild> {
-        let mut applied_variant = AppliedReproducibleBuild::new(&self);
         if let Some(name) = variant_name {
-            if let Some(variant) = self.variants_map.get(name) {
-                applied_variant.inject_variant_build(name, variant);
-            } else {
+            if !self.variants_map.contains_key(name) {
                 println!(
                     "{}{}{}",
                     "Build variant called `".yellow(),
@@ -215,6 +259,148 @@ impl parse::ReproducibleBuild {
                 ));
             }
         }
+        self.apply(variant_name, true)
+    }
+
+    /// default `[package.metadata.near.reproducible_build]`, followed by all of its variants
+    /// in alphabetical order, with `extends` resolved
+    pub fn resolve_all(&self) -> eyre::Result<Vec<AppliedReproducibleBuild>> {
+        std::iter::once(None)
+            .chain(self.variants_map.keys().map(|name| Some(name.as_str())))
+            .map(|variant_name| self.apply(variant_name, false))
+            .collect()
+    }
+
+    fn apply(
+        &self,
+        variant_name: Option<&str>,
+        verbose: bool,
+    ) -> eyre::Result<AppliedReproducibleBuild> {
+        let mut applied_variant = AppliedReproducibleBuild::new(self);
+        if let Some(name) = variant_name {
+            let chain = self.variant_chain(name)?;
+            for (variant_name, variant) in chain.iter().rev() {
+                applied_variant.inject_variant_build(variant_name, variant, verbose);
+            }
+            applied_variant.extended_variants = chain
+                .iter()
+                .skip(1)
+                .map(|(variant_name, _)| variant_name.to_string())
+                .collect();
+        }
         Ok(applied_variant)
     }
+
+    /// `variant_name` followed by variants it extends, from the nearest to the most distant one
+    fn variant_chain<'a>(
+        &'a self,
+        variant_name: &'a str,
+    ) -> eyre::Result<Vec<(&'a str, &'a parse::VariantReproducibleBuild)>> {
+        let mut chain: Vec<(&str, &parse::VariantReproducibleBuild)> = vec![];
+        let mut next = Some(variant_name);
+        while let Some(name) = next {
+            if chain.iter().any(|(visited, _)| *visited == name) {
+                let cycle = chain
+                    .iter()
+                    .map(|(visited, _)| *visited)
+                    .chain(std::iter::once(name))
+                    .collect::<Vec<_>>();
+                return Err(eyre::eyre!(
+                    "Malformed `{}` in Cargo.toml:\n`extends` of variants forms a cycle: {}",
+                    section_name(Some(&variant_name.to_string())),
+                    cycle.join(" -> ")
+                ));
+            }
+            let variant = match self.variants_map.get(name) {
+                Some(variant) => variant,
+                None => {
+                    let (extending, _) = chain
+                        .last()
+                        .expect("`variant_name` is checked to be present by callers");
+                    return Err(eyre::eyre!(
+                        "Malformed `{}` in Cargo.toml:\n`extends`: variant `{}` isn't defined",
+                        section_name(Some(&extending.to_string())),
+                        name
+                    ));
+                }
+            };
+            chain.push((name, variant));
+            next = variant.extends.as_deref();
+        }
+        Ok(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse::ReproducibleBuild;
+
+    fn reproducible_build(variants: serde_json::Value) -> ReproducibleBuild {
+        serde_json::from_value(serde_json::json!({
+            "image": "sourcescan/cargo-near:0.13.4-rust-1.85.0",
+            "image_digest": "sha256:a9d8bee7b134856cc8baa142494a177f2ba9ecfededfcdd38f634e14cca8aae2",
+            "passed_env": ["KEY"],
+            "container_build_command": ["cargo", "near", "build", "non-reproducible-wasm", "--locked"],
+            "variant": variants,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn extends_chain_is_applied_from_the_most_distant_variant() {
+        let build = reproducible_build(serde_json::json!({
+            "testnet": { "passed_env": ["TESTNET_KEY"], "image_digest": "sha256:testnet" },
+            "testnet-debug": { "extends": "testnet", "image_digest": "sha256:debug" },
+        }));
+        let applied = build
+            .apply_variant_or_default(Some("testnet-debug"))
+            .unwrap();
+        assert_eq!(applied.selected_variant.as_deref(), Some("testnet-debug"));
+        assert_eq!(applied.extended_variants, vec!["testnet".to_string()]);
+        assert_eq!(applied.image_digest, "sha256:debug");
+        assert_eq!(applied.passed_env, Some(vec!["TESTNET_KEY".to_string()]));
+        assert_eq!(applied.image, "sourcescan/cargo-near:0.13.4-rust-1.85.0");
+    }
+
+    #[test]
+    fn extends_cycle_and_missing_variant_are_rejected() {
+        let build = reproducible_build(serde_json::json!({
+            "a": { "extends": "b" },
+            "b": { "extends": "a" },
+            "c": { "extends": "absent" },
+        }));
+        let err = build.resolve_all().err().unwrap().to_string();
+        assert!(err.contains("cycle: a -> b -> a"), "{err}");
+
+        let build = reproducible_build(serde_json::json!({
+            "c": { "extends": "absent" },
+        }));
+        let err = build.resolve_all().err().unwrap().to_string();
+        assert!(
+            err.contains("variant.c]") && err.contains("`absent` isn't defined"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn resolve_all_lists_default_then_variants() {
+        let build = reproducible_build(serde_json::json!({
+            "mainnet": {},
+            "testnet": { "extends": "mainnet" },
+        }));
+        let names = build
+            .resolve_all()
+            .unwrap()
+            .into_iter()
+            .map(|applied| applied.selected_variant)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                None,
+                Some("mainnet".to_string()),
+                Some("testnet".to_string())
+            ]
+        );
+    }
 }
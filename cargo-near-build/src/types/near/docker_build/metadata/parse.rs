@@ -4,8 +4,6 @@ use serde::Deserialize;
 use serde_json::Value;
 use std::{collections::BTreeMap, thread, time::Duration};
 
-use std::str::FromStr;
-
 use crate::types::cargo::metadata::CrateMetadata;
 
 #[derive(Deserialize, Debug)]
@@ -15,8 +13,12 @@ pub struct ReproducibleBuild {
     pub image_digest: String,
     pub passed_env: Option<Vec<String>>,
     pub container_build_command: Option<Vec<String>>,
-    /// a cloneable git remote url,
-    /// currently, only ones, starting with `https://`, are supported;
+    /// globs of files, relative to the root of the repository, which are allowed to be
+    /// modified or untracked, when git checks of reproducible builds are performed
+    #[serde(default)]
+    pub dirty_allowlist: Vec<String>,
+    /// a cloneable git remote url, either `https://` or `ssh://` one,
+    /// scp-like `git@host:org/repo.git` form is converted to the latter;
     /// parsed from `package.repository`
     #[serde(skip)]
     pub repository: Option<url::Url>,
@@ -31,6 +33,9 @@ pub struct ReproducibleBuild {
 #[derive(Deserialize, Debug)]
 /// parsed from `[package.metadata.near.reproducible_build.variant.name]` in Cargo.toml
 pub struct VariantReproducibleBuild {
+    /// name of another variant, which this one is applied on top of,
+    /// instead of default `[package.metadata.near.reproducible_build]`
+    pub extends: Option<String>,
     pub image: Option<String>,
     pub image_digest: Option<String>,
     pub passed_env: Option<Vec<String>>,
@@ -84,9 +89,26 @@ impl ReproducibleBuild {
             .root_package
             .repository
             .as_deref()
-            .map(<url::Url as FromStr>::from_str)
+            .map(super::repository::parse)
             .transpose()?;
 
         Ok(build_meta)
     }
+
+    /// `dirty_allowlist` of `[package.metadata.near.reproducible_build]`,
+    /// which is empty if the section is missing
+    pub fn parse_dirty_allowlist(cargo_metadata: &CrateMetadata) -> eyre::Result<Vec<String>> {
+        let Some(dirty_allowlist) = cargo_metadata
+            .root_package
+            .metadata
+            .get("near")
+            .and_then(|value| value.get("reproducible_build"))
+            .and_then(|value| value.get("dirty_allowlist"))
+        else {
+            return Ok(vec![]);
+        };
+        serde_json::from_value(dirty_allowlist.clone()).wrap_err(
+            "Malformed `dirty_allowlist` of `[package.metadata.near.reproducible_build]` in Cargo.toml",
+        )
+    }
 }
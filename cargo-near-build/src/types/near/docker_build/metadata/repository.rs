@@ -0,0 +1,109 @@
+use std::str::FromStr;
+
+/// parses `package.repository` into a url;
+/// scp-like `[user@]host:path` form, e.g. `git@github.com:org/repo.git`,
+/// is converted into `ssh://[user@]host/path`
+pub fn parse(repository: &str) -> eyre::Result<url::Url> {
+    if let Some(url) = scp_like_to_ssh(repository) {
+        return Ok(url);
+    }
+    url::Url::from_str(repository)
+        .map_err(|err| eyre::eyre!("`{}` isn't a valid repository url: {}", repository, err))
+}
+
+fn scp_like_to_ssh(repository: &str) -> Option<url::Url> {
+    if repository.contains("://") {
+        return None;
+    }
+    let (user_host, path) = repository.split_once(':')?;
+    // a colon after a slash is a part of a local path, not a host separator
+    if user_host.is_empty() || user_host.contains('/') || path.is_empty() {
+        return None;
+    }
+    let path = path.trim_start_matches('/');
+    url::Url::from_str(&format!("ssh://{user_host}/{path}")).ok()
+}
+
+/// hosts, for which `ssh` remotes are served over `https` on the same host and path;
+/// others (self-hosted forges, Bitbucket Server, Azure DevOps, ssh config aliases)
+/// don't follow this scheme or can't be told apart from ones that do
+const SSH_HOSTS_WITH_SAME_HTTPS_PATH: &[&str] =
+    &["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"];
+
+/// canonical `https` form of a repository url, which is recorded in NEP-330 metadata;
+/// user and port of `ssh` urls are dropped, as they're specific to ssh access;
+/// `ssh` urls are only converted for [`SSH_HOSTS_WITH_SAME_HTTPS_PATH`]
+pub fn to_https(repository: &url::Url) -> eyre::Result<url::Url> {
+    match repository.scheme() {
+        "https" => Ok(repository.clone()),
+        "ssh" => {
+            let host = repository
+                .host_str()
+                .ok_or_else(|| eyre::eyre!("`{}` has no host", repository))?;
+            if !SSH_HOSTS_WITH_SAME_HTTPS_PATH.contains(&host) {
+                return Err(eyre::eyre!(
+                    "`https` url of the repository can't be derived reliably from `ssh` one for `{}` host, \
+                     as it's only known for {}; \
+                     set `[package.repository]` to `https` url of the repository instead",
+                    host,
+                    SSH_HOSTS_WITH_SAME_HTTPS_PATH.join(", "),
+                ));
+            }
+            url::Url::from_str(&format!("https://{}{}", host, repository.path())).map_err(|err| {
+                eyre::eyre!("`{}` can't be converted to `https`: {}", repository, err)
+            })
+        }
+        scheme => Err(eyre::eyre!(
+            "`{}` scheme isn't supported, only `https`, `ssh` and scp-like `git@host:org/repo` remotes are supported at the moment",
+            scheme
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scp_like_and_ssh_urls_are_normalized_to_https() {
+        for (repository, cloneable, https) in [
+            (
+                "git@github.com:org/repo.git",
+                "ssh://git@github.com/org/repo.git",
+                "https://github.com/org/repo.git",
+            ),
+            (
+                "ssh://git@gitlab.com:2222/group/sub/repo",
+                "ssh://git@gitlab.com:2222/group/sub/repo",
+                "https://gitlab.com/group/sub/repo",
+            ),
+            (
+                "https://git.example.com/scm/proj/repo",
+                "https://git.example.com/scm/proj/repo",
+                "https://git.example.com/scm/proj/repo",
+            ),
+        ] {
+            let url = parse(repository).unwrap();
+            assert_eq!(url.as_str(), cloneable);
+            assert_eq!(to_https(&url).unwrap().as_str(), https);
+        }
+        assert!(to_https(&parse("git://github.com/org/repo").unwrap()).is_err());
+        assert!(parse("./relative/path").is_err());
+    }
+
+    #[test]
+    fn ssh_urls_of_unknown_hosts_are_not_converted() {
+        for repository in [
+            "git@github-work:org/repo.git",
+            "ssh://git@bitbucket.example.com:7999/proj/repo.git",
+            "git@ssh.dev.azure.com:v3/org/project/repo",
+        ] {
+            let err = to_https(&parse(repository).unwrap()).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("set `[package.repository]` to `https` url"),
+                "{repository}: {err}"
+            );
+        }
+    }
+}
@@ -109,16 +109,16 @@ impl super::AppliedReproducibleBuild {
 
     fn validate_repository(&self) -> eyre::Result<()> {
         if let Some(ref repository) = self.repository {
-            if repository.scheme() != "https" {
-                Err(eyre::eyre!(
-                    "{}: {}\n{}",
-                    "Malformed NEP330 metadata in Cargo.toml:",
-                    repository,
-                    "`[package.repository]`: only `https` scheme is supported at the moment",
-                ))
-            } else {
-                Ok(())
-            }
+            super::repository::to_https(repository)
+                .map(|_| ())
+                .map_err(|err| {
+                    eyre::eyre!(
+                        "{}: {}\n`[package.repository]`: {}",
+                        "Malformed NEP330 metadata in Cargo.toml",
+                        repository,
+                        err,
+                    )
+                })
         } else {
             Err(eyre::eyre!(
                 "{}: \n{}",
@@ -4,11 +4,15 @@ use crate::types::cargo::manifest_path::ManifestPath;
 use super::build::input::BuildContext;
 
 pub mod cloned_repo;
+pub mod container_runtime;
 pub mod crate_in_repo;
 pub mod metadata;
 
 mod compute_command;
 pub mod subprocess;
+pub mod upgrade_image;
+pub mod vendor;
+pub mod verify;
 
 #[derive(Default, Debug, Clone, bon::Builder)]
 pub struct Opts {
@@ -24,10 +28,38 @@ pub struct Opts {
     pub color: Option<ColorPreference>,
     /// Variant of the reproducible-wasm build
     pub variant: Option<String>,
+    /// Build the contract from a remote git repository at a given revision,
+    /// instead of the local repository, containing `manifest_path`
+    pub remote: Option<RemoteSource>,
+    /// Build the contract at this revision (commit, tag or branch) of the local repository
+    /// instead of current HEAD; uncommitted changes aren't checked then
+    pub git_ref: Option<String>,
+    /// globs of files, relative to the root of the repository, which are allowed to be
+    /// modified or untracked, in addition to `dirty_allowlist` of `[package.metadata.near.reproducible_build]`
+    #[builder(default)]
+    pub allow_dirty: Vec<String>,
+    /// Vendor dependencies on the temporary build site with `cargo vendor`
+    /// and build in a container without network access
+    #[builder(default)]
+    pub vendor: bool,
+    /// Container runtime to build with;
+    /// resolved with [`ContainerRuntime::resolve`](container_runtime::ContainerRuntime::resolve) when `None`
+    pub container_runtime: Option<container_runtime::ContainerRuntime>,
     #[builder(default)]
     pub context: BuildContext,
 }
 
+/// used as field in [`DockerBuildOpts`](crate::docker::DockerBuildOpts)
+#[derive(Debug, Clone)]
+pub struct RemoteSource {
+    /// url of the git repository to clone
+    pub repo: String,
+    /// revision (commit, tag or branch) to check out
+    pub rev: String,
+    /// path of the contract's crate, relative to the root of the repository
+    pub contract_path: camino::Utf8PathBuf,
+}
+
 pub const WARN_BECOMES_ERR: &str =
     "This WARNING becomes a hard ERROR when deploying contract with docker.";
 
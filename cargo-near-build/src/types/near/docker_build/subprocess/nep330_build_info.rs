@@ -1,5 +1,6 @@
 use crate::docker::DockerBuildOpts;
-use crate::types::near::docker_build::{cloned_repo, metadata};
+use crate::types::near::build::checksum::SHA256Checksum;
+use crate::types::near::docker_build::{cloned_repo, metadata, vendor};
 use eyre::ContextCompat;
 use near_verify_rs::types::source_id;
 
@@ -20,7 +21,7 @@ fn compute_repo_link_hint(
     applied_build_meta: &metadata::AppliedReproducibleBuild,
     cloned_repo: &cloned_repo::ClonedRepo,
 ) -> Option<String> {
-    let repo_link_url = applied_build_meta.repository.clone().expect(
+    let repo_link_url = applied_build_meta.https_repository().expect(
         "expected to be [Option::Some] due to [metadata::ReproducibleBuild::validate_repository] rule"
     );
     let revision = cloned_repo.initial_crate_in_repo.head.to_string();
@@ -49,6 +50,7 @@ impl BuildInfoMixed {
         opts: &DockerBuildOpts,
         applied_build_meta: &metadata::AppliedReproducibleBuild,
         cloned_repo: &cloned_repo::ClonedRepo,
+        vendored_sources: Option<&SHA256Checksum>,
     ) -> eyre::Result<Self> {
         let build_environment = applied_build_meta.concat_image();
         let contract_path = cloned_repo
@@ -60,13 +62,16 @@ impl BuildInfoMixed {
 
         let source_code_snapshot = source_id::SourceId::for_git(
             // this unwrap depends on `metadata::ReproducibleBuild::validate` logic
-            applied_build_meta.repository.as_ref().unwrap(),
+            &applied_build_meta.https_repository().unwrap(),
             source_id::GitReference::Rev(cloned_repo.initial_crate_in_repo.head.to_string()),
         )
         .map_err(|err| eyre::eyre!("compute SourceId {}", err))?;
 
         let link = compute_repo_link_hint(applied_build_meta, cloned_repo);
-        let build_command = opts.get_cli_build_command_in_docker(applied_build_meta)?;
+        let mut build_command = opts.get_cli_build_command_in_docker(applied_build_meta)?;
+        if let Some(vendored_sources) = vendored_sources {
+            vendor::record_in_build_command(&mut build_command, vendored_sources);
+        }
 
         Ok(Self {
             build_environment,
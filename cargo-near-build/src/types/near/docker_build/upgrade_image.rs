@@ -0,0 +1,49 @@
+use std::str::FromStr;
+
+use crate::ColorPreference;
+use crate::near::docker_build::warn_versions_upgrades::SOURCE_SCAN_TAG_PATTERN;
+
+/// tag of [`SOURCE_SCAN_IMAGE`](crate::docker::upgrade_image::SOURCE_SCAN_IMAGE),
+/// matching [`SOURCE_SCAN_TAG_PATTERN`], along with its digest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageTag {
+    /// e.g. `0.13.4-rust-1.85.0`
+    pub tag: String,
+    /// version of `cargo-near` in the image
+    pub cargo_near: cargo_metadata::semver::Version,
+    /// version of rust toolchain in the image
+    pub rust: cargo_metadata::semver::Version,
+    /// e.g. `sha256:a9d8bee7b134856cc8baa142494a177f2ba9ecfededfcdd38f634e14cca8aae2`
+    pub image_digest: String,
+}
+
+impl ImageTag {
+    /// `None` if `tag` doesn't match [`SOURCE_SCAN_TAG_PATTERN`]
+    pub fn parse(tag: &str, image_digest: &str) -> Option<Self> {
+        let regex = regex::Regex::new(SOURCE_SCAN_TAG_PATTERN).expect("no error");
+        let captures = regex.captures(tag)?;
+        Some(Self {
+            tag: tag.to_string(),
+            cargo_near: cargo_metadata::semver::Version::from_str(captures.get(1)?.as_str())
+                .ok()?,
+            rust: cargo_metadata::semver::Version::from_str(captures.get(5)?.as_str()).ok()?,
+            image_digest: image_digest.to_string(),
+        })
+    }
+}
+
+/// Argument of [`upgrade_image`](crate::docker::upgrade_image::upgrade_image)
+#[derive(Debug, Clone, bon::Builder)]
+pub struct Opts {
+    /// Path to the `Cargo.toml` of the contract;
+    /// `Cargo.toml` in current directory is assumed when `None`
+    pub manifest_path: Option<camino::Utf8PathBuf>,
+    /// `[package.metadata.near.reproducible_build.variant.<variant>]` to upgrade the image of,
+    /// instead of default `[package.metadata.near.reproducible_build]`
+    pub variant: Option<String>,
+    /// image tag to upgrade to
+    pub tag: ImageTag,
+    /// Coloring: auto, always, never;
+    /// assumed to be auto when `None`
+    pub color: Option<ColorPreference>,
+}
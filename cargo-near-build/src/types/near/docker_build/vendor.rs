@@ -0,0 +1,269 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use colored::Colorize;
+use eyre::WrapErr;
+use sha2::{Digest, Sha256};
+
+use crate::types::near::build::checksum::SHA256Checksum;
+
+/// directory with vendored dependencies, relative to contract's crate
+pub const VENDOR_DIR: &str = "cargo-near-vendor";
+
+const CARGO_CONFIG: &str = ".cargo/config.toml";
+
+/// environment variable, passed with `--env` in `build_command` of NEP-330 build info of a vendored build;
+/// its value is hex of sha256 of vendored sources, so that the build is replayed with vendoring
+/// by `cargo near verify`, and vendored sources are checked to be the same
+pub const BUILD_COMMAND_ENV_KEY: &str = "CARGO_NEAR_VENDORED_SOURCES";
+
+/// appends `--env CARGO_NEAR_VENDORED_SOURCES=<checksum>` to `build_command` of a vendored build
+pub(crate) fn record_in_build_command(build_command: &mut Vec<String>, checksum: &SHA256Checksum) {
+    build_command.extend([
+        "--env".to_string(),
+        format!("{}={}", BUILD_COMMAND_ENV_KEY, checksum.to_hex_string()),
+    ]);
+}
+
+/// checksum of vendored sources, recorded in `build_command` by [`record_in_build_command`],
+/// `None` if the build wasn't vendored
+pub(crate) fn recorded_in_build_command(
+    build_command: &[String],
+) -> eyre::Result<Option<SHA256Checksum>> {
+    let prefix = format!("{BUILD_COMMAND_ENV_KEY}=");
+    let Some(value) = build_command
+        .windows(2)
+        .filter(|pair| pair[0] == "--env")
+        .find_map(|pair| pair[1].strip_prefix(&prefix))
+    else {
+        return Ok(None);
+    };
+    let hash = hex::decode(value)
+        .ok()
+        .filter(|hash| hash.len() == 32)
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "`{BUILD_COMMAND_ENV_KEY}` in `build_command` isn't a hex SHA-256 hash: `{value}`"
+            )
+        })?;
+    Ok(Some(SHA256Checksum { hash }))
+}
+
+/// runs `cargo vendor` for the crate at `crate_dir`, and appends source replacement,
+/// printed by it, to `.cargo/config.toml` of the crate, so that the crate builds without network access;
+/// returns checksum of vendored sources
+pub(crate) fn vendor(crate_dir: &Utf8Path, no_locked: bool) -> eyre::Result<SHA256Checksum> {
+    let vendor_dir = crate_dir.join(VENDOR_DIR);
+    if vendor_dir.exists() {
+        return Err(eyre::eyre!(
+            "`{}` already exists in the repository, it can't be used for vendored dependencies",
+            vendor_dir
+        ));
+    }
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut cmd = std::process::Command::new(cargo);
+    cmd.current_dir(crate_dir)
+        .args(["vendor", "--versioned-dirs", VENDOR_DIR]);
+    if !no_locked {
+        cmd.arg("--locked");
+    }
+    tracing::info!(
+        target: "near_teach_me",
+        parent: &tracing::Span::none(),
+        "Invoking cargo:\n{}",
+        crate::pretty_print::indent_payload(&format!("{cmd:#?}"))
+    );
+    let output = cmd
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .wrap_err_with(|| format!("Error executing `{cmd:?}`"))?;
+    if !output.status.success() {
+        return Err(eyre::eyre!("`cargo vendor` failed: {}", output.status));
+    }
+    // `VENDOR_DIR` is passed as a relative path, so it's printed relative in source replacement,
+    // which stays valid, when the crate is mounted into a container
+    let source_replacement = String::from_utf8(output.stdout)?;
+
+    let checksum = checksum(&vendor_dir)?;
+    append_source_replacement(crate_dir, &checksum, &source_replacement)?;
+
+    println!(
+        "{} {}",
+        "sha256 of vendored sources:".green(),
+        checksum.to_hex_string()
+    );
+    Ok(checksum)
+}
+
+/// existing `.cargo/config.toml` of the crate is kept, `source_replacement` is appended to it
+fn append_source_replacement(
+    crate_dir: &Utf8Path,
+    checksum: &SHA256Checksum,
+    source_replacement: &str,
+) -> eyre::Result<()> {
+    let config_path = crate_dir.join(CARGO_CONFIG);
+    let mut config = match std::fs::read_to_string(&config_path) {
+        Ok(existing) => existing + "\n",
+        Err(_) => String::new(),
+    };
+    config.push_str(&format!(
+        "# generated by cargo-near for a build without network access\n\
+         # sha256 of vendored sources: {}\n{}",
+        checksum.to_hex_string(),
+        source_replacement
+    ));
+    std::fs::create_dir_all(crate_dir.join(".cargo"))?;
+    std::fs::write(&config_path, config)?;
+    Ok(())
+}
+
+/// SHA-256 of relative paths and contents of all files in `dir`, in sorted order of paths
+fn checksum(dir: &Utf8Path) -> eyre::Result<SHA256Checksum> {
+    let mut files = vec![];
+    collect_files(dir, &mut files)?;
+    files.sort();
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(dir)?;
+        let contents = std::fs::read(&file)?;
+        hasher.update(relative.as_str().as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(SHA256Checksum {
+        hash: hasher.finalize().to_vec(),
+    })
+}
+
+fn collect_files(dir: &Utf8Path, files: &mut Vec<Utf8PathBuf>) -> eyre::Result<()> {
+    for entry in dir.read_dir_utf8()? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            collect_files(entry.path(), files)?;
+        } else {
+            files.push(entry.path().to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE_REPLACEMENT: &str = r#"[source.crates-io]
+replace-with = "vendored-sources"
+
+[source.vendored-sources]
+directory = "cargo-near-vendor"
+"#;
+
+    fn write_files(dir: &Utf8Path, files: &[(&str, &str)]) {
+        for (path, contents) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    fn checksum_of(files: &[(&str, &str)]) -> SHA256Checksum {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        write_files(dir, files);
+        checksum(dir).unwrap()
+    }
+
+    #[test]
+    fn checksum_depends_on_paths_and_contents_only() {
+        let files = [
+            ("serde-1.0.0/Cargo.toml", "[package]"),
+            ("serde-1.0.0/src/lib.rs", "pub fn a() {}"),
+            ("borsh-1.5.0/src/lib.rs", "pub fn b() {}"),
+        ];
+        let mut reversed = files;
+        reversed.reverse();
+        assert_eq!(checksum_of(&files), checksum_of(&files));
+        // files are hashed in sorted order of paths, regardless of order of their creation
+        assert_eq!(checksum_of(&files), checksum_of(&reversed));
+
+        let renamed = [
+            ("serde-1.0.0/Cargo.toml", "[package]"),
+            ("serde-1.0.0/src/lib2.rs", "pub fn a() {}"),
+            ("borsh-1.5.0/src/lib.rs", "pub fn b() {}"),
+        ];
+        let modified = [
+            ("serde-1.0.0/Cargo.toml", "[package]"),
+            ("serde-1.0.0/src/lib.rs", "pub fn a() { }"),
+            ("borsh-1.5.0/src/lib.rs", "pub fn b() {}"),
+        ];
+        // contents are length-prefixed, so moving bytes between a path and contents changes the hash
+        let shifted = [
+            ("serde-1.0.0/Cargo.toml", "[package]"),
+            ("serde-1.0.0/src/lib.r", "spub fn a() {}"),
+            ("borsh-1.5.0/src/lib.rs", "pub fn b() {}"),
+        ];
+        for other in [renamed, modified, shifted] {
+            assert_ne!(checksum_of(&files), checksum_of(&other), "{other:?}");
+        }
+    }
+
+    #[test]
+    fn checksum_is_recorded_in_build_command() {
+        let checksum = SHA256Checksum {
+            hash: vec![0xab; 32],
+        };
+        let mut build_command = [
+            "cargo",
+            "near",
+            "build",
+            "non-reproducible-wasm",
+            "--locked",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(recorded_in_build_command(&build_command).unwrap(), None);
+
+        record_in_build_command(&mut build_command, &checksum);
+        assert_eq!(
+            build_command[5..],
+            [
+                "--env".to_string(),
+                format!("CARGO_NEAR_VENDORED_SOURCES={}", "ab".repeat(32))
+            ]
+        );
+        assert_eq!(
+            recorded_in_build_command(&build_command).unwrap(),
+            Some(checksum)
+        );
+
+        let invalid = ["--env", "CARGO_NEAR_VENDORED_SOURCES=abab"].map(String::from);
+        assert!(recorded_in_build_command(&invalid).is_err());
+    }
+
+    #[test]
+    fn source_replacement_is_appended_to_existing_config() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let crate_dir = Utf8Path::from_path(tmp_dir.path()).unwrap();
+        let checksum = SHA256Checksum {
+            hash: vec![0xab; 32],
+        };
+
+        append_source_replacement(crate_dir, &checksum, SOURCE_REPLACEMENT).unwrap();
+        let config = std::fs::read_to_string(crate_dir.join(CARGO_CONFIG)).unwrap();
+        assert!(config.starts_with("# generated by cargo-near"), "{config}");
+        assert!(config.contains(&checksum.to_hex_string()), "{config}");
+        assert!(config.ends_with(SOURCE_REPLACEMENT), "{config}");
+
+        let existing = "[build]\nrustflags = [\"-C\", \"link-arg=-s\"]\n";
+        write_files(crate_dir, &[(CARGO_CONFIG, existing)]);
+        append_source_replacement(crate_dir, &checksum, SOURCE_REPLACEMENT).unwrap();
+        let config = std::fs::read_to_string(crate_dir.join(CARGO_CONFIG)).unwrap();
+        assert!(config.starts_with(existing), "{config}");
+        assert!(config.ends_with(SOURCE_REPLACEMENT), "{config}");
+        let parsed: toml_edit::DocumentMut = config.parse().unwrap();
+        assert_eq!(
+            parsed["source"]["crates-io"]["replace-with"].as_str(),
+            Some("vendored-sources")
+        );
+        assert!(parsed["build"]["rustflags"].is_array());
+    }
+}
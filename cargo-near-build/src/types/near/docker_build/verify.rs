@@ -0,0 +1,28 @@
+use crate::ColorPreference;
+
+use super::container_runtime::ContainerRuntime;
+
+/// Argument of [`verify`](crate::docker::verify::verify)
+#[derive(Debug, Clone, bon::Builder)]
+pub struct Opts {
+    /// Path to a JSON file with NEP-330 `ContractSourceMetadata` of the contract;
+    /// if `None`, it's extracted from [`ExpectedHash::Wasm`]
+    pub metadata: Option<camino::Utf8PathBuf>,
+    /// wasm, which reproduced build is compared against
+    pub expected: ExpectedHash,
+    /// Container runtime to build with;
+    /// resolved with [`ContainerRuntime::resolve`] when `None`
+    pub container_runtime: Option<ContainerRuntime>,
+    /// Coloring: auto, always, never;
+    /// assumed to be auto when `None`
+    pub color: Option<ColorPreference>,
+}
+
+/// used as field in [`VerifyOpts`](crate::docker::verify::VerifyOpts)
+#[derive(Debug, Clone)]
+pub enum ExpectedHash {
+    /// local wasm file, its SHA-256 is computed
+    Wasm(camino::Utf8PathBuf),
+    /// SHA-256 of the wasm, either in hex or in base58 (the form of NEAR code hash)
+    Checksum(String),
+}
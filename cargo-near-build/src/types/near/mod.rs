@@ -7,6 +7,10 @@ pub mod abi;
 pub mod build;
 #[cfg(feature = "build_internal")]
 pub mod check;
+#[cfg(feature = "build_internal")]
+pub mod run_view;
+#[cfg(feature = "build_internal")]
+pub mod standards;
 
 #[cfg(feature = "build_external")]
 pub mod build_extended;
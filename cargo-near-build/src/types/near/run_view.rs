@@ -0,0 +1,31 @@
+use crate::ColorPreference;
+
+/// Argument of [`run_view`](crate::run_view::run_view)
+#[derive(Debug, Clone, bon::Builder)]
+pub struct Opts {
+    /// Path to contract's wasm
+    #[builder(into)]
+    pub wasm: camino::Utf8PathBuf,
+    /// Name of the view method to call
+    #[builder(into)]
+    pub method: String,
+    /// Arguments of the method, passed to it as-is (JSON for methods with JSON arguments)
+    #[builder(into)]
+    pub args: Option<String>,
+    /// Path to a JSON dump of contract's state, in the format of `view_state` RPC query result:
+    /// `{"values": [{"key": "<base64>", "value": "<base64>"}, ...]}`
+    #[builder(into)]
+    pub state: Option<camino::Utf8PathBuf>,
+    /// Coloring: auto, always, never;
+    /// assumed to be auto when `None`
+    pub color: Option<ColorPreference>,
+}
+
+/// Result of a view method call in an embedded interpreter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewOutcome {
+    /// value, returned by the method with `value_return` host function
+    pub return_value: Option<Vec<u8>>,
+    /// messages, logged by the method
+    pub logs: Vec<String>,
+}
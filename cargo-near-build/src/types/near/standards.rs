@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use eyre::WrapErr;
+
+use crate::types::cargo::metadata::CrateMetadata;
+
+/// one of NEAR standards, which the contract is expected to implement, parsed from
+/// `[package.metadata.near] standards = [{ standard = "nep141", version = "1.0.0" }]` in Cargo.toml;
+/// `near-sdk` embeds standards from `#[near(contract_metadata(standard(..)))]` of contract's code only,
+/// so these are checked against NEP-330 metadata of the built contract, see [`crate::near::build`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Standard {
+    /// standard name in lowercase NEP format, e.g. `nep141`
+    pub standard: String,
+    /// semantic version of the implemented standard, e.g. `1.0.0`
+    pub version: String,
+}
+
+impl Standard {
+    /// standards of the contract, an empty list if they're not specified
+    pub fn parse_all(crate_metadata: &CrateMetadata) -> eyre::Result<Vec<Self>> {
+        let Some(standards) = crate_metadata
+            .root_package
+            .metadata
+            .get("near")
+            .and_then(|value| value.get("standards"))
+        else {
+            return Ok(vec![]);
+        };
+        let standards: Vec<Self> = serde_json::from_value(standards.clone())
+            .wrap_err("Malformed `standards` of `[package.metadata.near]` in Cargo.toml")?;
+        Self::validate_all(&standards)
+            .wrap_err("Invalid `standards` of `[package.metadata.near]` in Cargo.toml")?;
+        Ok(standards)
+    }
+
+    fn validate_all(standards: &[Self]) -> eyre::Result<()> {
+        for (index, standard) in standards.iter().enumerate() {
+            let is_nep = standard.standard.strip_prefix("nep").is_some_and(|number| {
+                !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+            });
+            if !is_nep {
+                return Err(eyre::eyre!(
+                    "`{}` isn't a standard name in lowercase NEP format, e.g. `nep141`",
+                    standard.standard
+                ));
+            }
+            cargo_metadata::semver::Version::from_str(&standard.version).wrap_err_with(|| {
+                format!(
+                    "`{}` version of `{}` isn't a semantic version, e.g. `1.0.0`",
+                    standard.version, standard.standard
+                )
+            })?;
+            if standards[..index]
+                .iter()
+                .any(|other| other.standard == standard.standard)
+            {
+                return Err(eyre::eyre!(
+                    "`{}` is specified more than once",
+                    standard.standard
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard(standard: &str, version: &str) -> Standard {
+        Standard {
+            standard: standard.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn standards_are_validated() {
+        let standards = vec![standard("nep141", "1.0.0"), standard("nep145", "1.1.0")];
+        Standard::validate_all(&standards).unwrap();
+
+        for invalid in [
+            vec![standard("NEP141", "1.0.0")],
+            vec![standard("nep", "1.0.0")],
+            vec![standard("ft", "1.0.0")],
+            vec![standard("nep141", "1.0")],
+            vec![standard("nep141", "1.0.0"), standard("nep141", "2.0.0")],
+        ] {
+            assert!(Standard::validate_all(&invalid).is_err(), "{invalid:?}");
+        }
+    }
+}
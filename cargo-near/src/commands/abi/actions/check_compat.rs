@@ -0,0 +1,25 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::AbiCommandlContext)]
+#[interactive_clap(output_context = CheckCompatContext)]
+pub struct CheckCompat {
+    /// Git revision (commit, tag or branch) to compare current ABI against
+    ///
+    /// The revision is checked out into a temporary clone of the repo, and its ABI
+    /// is generated with the same features as the current one.
+    #[interactive_clap(long)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub against: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckCompatContext;
+
+impl CheckCompatContext {
+    pub fn from_previous_context(
+        previous_context: super::super::AbiCommandlContext,
+        scope: &<CheckCompat as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        cargo_near_build::abi::compat::check_compat(previous_context.opts, &scope.against)?;
+        Ok(Self)
+    }
+}
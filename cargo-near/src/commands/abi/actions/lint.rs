@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::AbiCommandlContext)]
+#[interactive_clap(output_context = LintContext)]
+pub struct Lint;
+
+#[derive(Debug, Clone)]
+pub struct LintContext;
+
+impl LintContext {
+    pub fn from_previous_context(
+        previous_context: super::super::AbiCommandlContext,
+        _scope: &<Lint as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        cargo_near_build::abi::lint::lint(previous_context.opts)?;
+        Ok(Self)
+    }
+}
@@ -0,0 +1,30 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::AbiCommandlContext)]
+#[interactive_clap(output_context = MigrateContext)]
+pub struct Migrate {
+    /// ABI `*.json` (or zstd-compressed `*.zst`) file, produced by an older version of `cargo-near`
+    pub path: crate::types::utf8_path_buf::Utf8PathBuf,
+    /// Write migrated ABI to this file instead of overwriting the original one
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub out: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MigrateContext;
+
+impl MigrateContext {
+    pub fn from_previous_context(
+        previous_context: super::super::AbiCommandlContext,
+        scope: &<Migrate as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let path: cargo_near_build::camino::Utf8PathBuf = scope.path.clone().into();
+        let out: Option<cargo_near_build::camino::Utf8PathBuf> = scope.out.clone().map(Into::into);
+        cargo_near_build::abi::migrate::migrate_file(
+            &path,
+            out.as_deref(),
+            previous_context.opts.compact_abi,
+        )?;
+        Ok(Self)
+    }
+}
@@ -0,0 +1,34 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::AbiCommandlContext)]
+#[interactive_clap(output_context = MockContext)]
+pub struct Mock {
+    /// ABI `*.json` (or zstd-compressed `*.zst`) file of the contract to mock
+    pub abi: crate::types::utf8_path_buf::Utf8PathBuf,
+    /// Directory to create the mock contract crate in, its name is used as package name
+    #[interactive_clap(long)]
+    pub out: crate::types::utf8_path_buf::Utf8PathBuf,
+    /// JSON file with results, returned by mocked functions, e.g. '{"get_price": {"multiplier": "1000"}}'
+    ///
+    /// Functions, absent from the file, return placeholder values, derived from their result schemas.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub responses: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MockContext;
+
+impl MockContext {
+    pub fn from_previous_context(
+        _previous_context: super::super::AbiCommandlContext,
+        scope: &<Mock as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let abi: cargo_near_build::camino::Utf8PathBuf = scope.abi.clone().into();
+        let out: cargo_near_build::camino::Utf8PathBuf = scope.out.clone().into();
+        let responses: Option<cargo_near_build::camino::Utf8PathBuf> =
+            scope.responses.clone().map(Into::into);
+        cargo_near_build::abi::mock::mock(&abi, &out, responses.as_deref())?;
+        Ok(Self)
+    }
+}
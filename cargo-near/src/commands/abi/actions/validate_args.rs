@@ -0,0 +1,34 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = super::super::AbiCommandlContext)]
+#[interactive_clap(output_context = ValidateArgsContext)]
+pub struct ValidateArgs {
+    /// Name of the contract's function, which is called with the arguments
+    pub method: String,
+    /// Arguments of the call as a JSON object, e.g. '{"owner_id": "alice.near"}'
+    pub args: String,
+    /// Read ABI from this `*.json` (or zstd-compressed `*.zst`) file instead of generating it
+    ///
+    /// e.g. the ABI file, produced by `cargo near build` next to the contract's wasm.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub abi_file: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidateArgsContext;
+
+impl ValidateArgsContext {
+    pub fn from_previous_context(
+        previous_context: super::super::AbiCommandlContext,
+        scope: &<ValidateArgs as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        cargo_near_build::abi::validate::validate_args(
+            previous_context.opts,
+            scope.abi_file.clone().map(Into::into),
+            &scope.method,
+            &scope.args,
+        )?;
+        Ok(Self)
+    }
+}
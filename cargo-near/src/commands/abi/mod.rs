@@ -1,8 +1,50 @@
 use cargo_near_build::abi::AbiOpts;
 
+pub mod actions {
+    pub mod check_compat;
+    pub mod lint;
+    pub mod migrate;
+    pub mod mock;
+    pub mod validate_args;
+
+    use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+    #[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
+    #[strum_discriminants(derive(EnumMessage, EnumIter))]
+    #[interactive_clap(context = super::AbiCommandlContext)]
+    pub enum Actions {
+        #[strum_discriminants(strum(
+            message = "lint          - Check generated ABI against rules, configured in `[package.metadata.near.abi.lints]`"
+        ))]
+        /// Check generated ABI against rules, configured in `[package.metadata.near.abi.lints]`
+        Lint(self::lint::Lint),
+        #[strum_discriminants(strum(
+            message = "check-compat  - Report ABI changes, which break callers of the contract built from another git revision"
+        ))]
+        /// Report ABI changes, which break callers of the contract built from another git revision
+        CheckCompat(self::check_compat::CheckCompat),
+        #[strum_discriminants(strum(
+            message = "validate-args - Validate JSON arguments of a function call against contract's ABI"
+        ))]
+        /// Validate JSON arguments of a function call against contract's ABI
+        ValidateArgs(self::validate_args::ValidateArgs),
+        #[strum_discriminants(strum(
+            message = "migrate       - Convert ABI file of an older schema version to the current one"
+        ))]
+        /// Convert ABI file of an older schema version to the current one
+        Migrate(self::migrate::Migrate),
+        #[strum_discriminants(strum(
+            message = "mock          - Generate a contract crate with the same interface as the one, described by an ABI file"
+        ))]
+        /// Generate a contract crate with the same interface as the one, described by an ABI file
+        Mock(self::mock::Mock),
+    }
+}
+
 #[derive(Debug, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(input_context = near_cli_rs::GlobalContext)]
 #[interactive_clap(output_context = AbiCommandlContext)]
+#[interactive_clap(skip_default_from_cli)]
 pub struct Command {
     /// Enable `--locked` flag for all `cargo` commands, disabled by default
     ///
@@ -47,45 +89,146 @@ pub struct Command {
     #[interactive_clap(skip_interactive_input)]
     #[interactive_clap(verbatim_doc_comment)]
     pub features: Option<String>,
+    /// Keep only functions, matching the pattern, in the ABI. This flag can be repeated.
+    ///
+    /// Pattern is either a glob on function name (`*` and `?` wildcards),
+    /// or one of the selectors: `@private`, `@payable`, `@init`, `@view`, `@call`.
+    /// Patterns are added to `include` list of `[package.metadata.near.abi]` in Cargo.toml.
+    #[interactive_clap(verbatim_doc_comment)]
+    #[interactive_clap(long_vec_multiple_opt)]
+    pub abi_include: Vec<String>,
+    /// Remove functions, matching the pattern, from the ABI. This flag can be repeated.
+    ///
+    /// e.g. `--abi-exclude @private --abi-exclude 'internal_*'`
+    /// Schema definitions, used only by removed functions, are removed too.
+    /// Patterns are added to `exclude` list of `[package.metadata.near.abi]` in Cargo.toml.
+    #[interactive_clap(verbatim_doc_comment)]
+    #[interactive_clap(long_vec_multiple_opt)]
+    pub abi_exclude: Vec<String>,
     /// Whether to color output to stdout and stderr by printing ANSI escape sequences: auto, always, never
     #[interactive_clap(long)]
     #[interactive_clap(value_enum)]
     #[interactive_clap(skip_interactive_input)]
     pub color: Option<crate::types::color_preference_cli::ColorPreferenceCli>,
+    /// Compare generated ABI with a committed ABI file instead of writing it into output directory
+    ///
+    /// `wasm_hash` and `build` info are ignored in comparison.
+    /// The command fails with a diff, if the generated ABI differs from the file.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub check: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Overwrite the file, specified with `--check`, with generated ABI, if they differ
+    #[interactive_clap(long)]
+    pub bless: bool,
+    /// ABI is generated into a file, if no subcommand is specified
+    #[interactive_clap(subcommand)]
+    pub actions: actions::Actions,
 }
 
-impl From<Command> for AbiOpts {
-    fn from(value: Command) -> Self {
-        Self {
-            no_locked: !value.locked,
-            no_doc: value.no_doc,
-            compact_abi: value.compact_abi,
-            out_dir: value.out_dir.map(Into::into),
-            manifest_path: value.manifest_path.map(Into::into),
-            features: value.features,
-            color: value.color.map(Into::into),
+impl interactive_clap::FromCli for Command {
+    type FromCliContext = near_cli_rs::GlobalContext;
+    type FromCliError = color_eyre::eyre::Error;
+    fn from_cli(
+        optional_clap_variant: Option<<Self as interactive_clap::ToCli>::CliVariant>,
+        context: Self::FromCliContext,
+    ) -> interactive_clap::ResultFromCli<
+        <Self as interactive_clap::ToCli>::CliVariant,
+        Self::FromCliError,
+    >
+    where
+        Self: Sized + interactive_clap::ToCli,
+    {
+        let mut clap_variant = optional_clap_variant.unwrap_or_default();
+        let new_context_scope = InteractiveClapContextScopeForCommand {
+            locked: clap_variant.locked,
+            no_doc: clap_variant.no_doc,
+            compact_abi: clap_variant.compact_abi,
+            out_dir: clap_variant.out_dir.clone(),
+            manifest_path: clap_variant.manifest_path.clone(),
+            features: clap_variant.features.clone(),
+            abi_include: clap_variant.abi_include.clone(),
+            abi_exclude: clap_variant.abi_exclude.clone(),
+            color: clap_variant.color.clone(),
+            check: clap_variant.check.clone(),
+            bless: clap_variant.bless,
+        };
+        let context = match AbiCommandlContext::from_previous_context(context, &new_context_scope) {
+            Ok(context) => context,
+            Err(err) => return interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+        };
+
+        let Some(cli_actions) = clap_variant.actions.take() else {
+            let result = match &context.check {
+                Some(path) => {
+                    cargo_near_build::abi::snapshot::check(context.opts, path, context.bless)
+                }
+                None => cargo_near_build::abi::build(context.opts).map(|_| ()),
+            };
+            return match result {
+                Ok(()) => interactive_clap::ResultFromCli::Ok(clap_variant),
+                Err(err) => interactive_clap::ResultFromCli::Err(Some(clap_variant), err),
+            };
+        };
+        if context.check.is_some() {
+            return interactive_clap::ResultFromCli::Err(
+                Some(clap_variant),
+                color_eyre::eyre::eyre!("`--check` can't be combined with a subcommand"),
+            );
+        }
+        match <actions::Actions as interactive_clap::FromCli>::from_cli(Some(cli_actions), context)
+        {
+            interactive_clap::ResultFromCli::Ok(cli_actions) => {
+                clap_variant.actions = Some(cli_actions);
+                interactive_clap::ResultFromCli::Ok(clap_variant)
+            }
+            interactive_clap::ResultFromCli::Cancel(optional_cli_actions) => {
+                clap_variant.actions = optional_cli_actions;
+                interactive_clap::ResultFromCli::Cancel(Some(clap_variant))
+            }
+            interactive_clap::ResultFromCli::Back => interactive_clap::ResultFromCli::Back,
+            interactive_clap::ResultFromCli::Err(optional_cli_actions, err) => {
+                clap_variant.actions = optional_cli_actions;
+                interactive_clap::ResultFromCli::Err(Some(clap_variant), err)
+            }
         }
     }
 }
 
+/// Arguments of `cargo near abi`, shared with its subcommands
 #[derive(Debug, Clone)]
-pub struct AbiCommandlContext;
+pub struct AbiCommandlContext {
+    pub opts: AbiOpts,
+    /// committed ABI file to compare generated ABI with, instead of writing it
+    pub check: Option<cargo_near_build::camino::Utf8PathBuf>,
+    pub bless: bool,
+}
 
 impl AbiCommandlContext {
     pub fn from_previous_context(
         _previous_context: near_cli_rs::GlobalContext,
         scope: &<Command as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
     ) -> color_eyre::eyre::Result<Self> {
-        let args = Command {
-            locked: scope.locked,
+        let opts = AbiOpts {
+            no_locked: !scope.locked,
             no_doc: scope.no_doc,
             compact_abi: scope.compact_abi,
-            out_dir: scope.out_dir.clone(),
-            manifest_path: scope.manifest_path.clone(),
+            out_dir: scope.out_dir.clone().map(Into::into),
+            manifest_path: scope.manifest_path.clone().map(Into::into),
             features: scope.features.clone(),
-            color: scope.color.clone(),
+            abi_include: scope.abi_include.clone(),
+            abi_exclude: scope.abi_exclude.clone(),
+            color: scope.color.clone().map(Into::into),
         };
-        cargo_near_build::abi::build(args.into())?;
-        Ok(Self)
+        if scope.bless && scope.check.is_none() {
+            return Err(color_eyre::eyre::eyre!(
+                "`--bless` requires `--check <path>` of the ABI file to update"
+            ));
+        }
+        Ok(Self {
+            opts,
+            check: scope.check.clone().map(Into::into),
+            bless: scope.bless,
+        })
     }
 }
@@ -96,6 +96,22 @@ pub struct BuildOpts {
     #[interactive_clap(skip_interactive_input)]
     #[interactive_clap(verbatim_doc_comment)]
     pub abi_features: Option<String>,
+    /// Keep only functions, matching the pattern, in the ABI. This flag can be repeated.
+    ///
+    /// Pattern is either a glob on function name (`*` and `?` wildcards),
+    /// or one of the selectors: `@private`, `@payable`, `@init`, `@view`, `@call`.
+    /// Patterns are added to `include` list of `[package.metadata.near.abi]` in Cargo.toml.
+    #[interactive_clap(verbatim_doc_comment)]
+    #[interactive_clap(long_vec_multiple_opt)]
+    pub abi_include: Vec<String>,
+    /// Remove functions, matching the pattern, from the ABI. This flag can be repeated.
+    ///
+    /// e.g. `--abi-exclude @private --abi-exclude 'internal_*'`
+    /// Schema definitions, used only by removed functions, are removed too.
+    /// Patterns are added to `exclude` list of `[package.metadata.near.abi]` in Cargo.toml.
+    #[interactive_clap(verbatim_doc_comment)]
+    #[interactive_clap(long_vec_multiple_opt)]
+    pub abi_exclude: Vec<String>,
     /// Do not activate the `default` feature of contract's crate
     ///
     /// This just passes `--no-default-features` argument to downstream `cargo` command.
@@ -149,6 +165,8 @@ impl From<CliBuildOpts> for BuildOpts {
             manifest_path: value.manifest_path,
             features: value.features,
             abi_features: value.abi_features,
+            abi_include: value.abi_include,
+            abi_exclude: value.abi_exclude,
             no_default_features: value.no_default_features,
             color: value.color,
             env: value.env,
@@ -178,6 +196,8 @@ pub mod context {
                 no_wasmopt: scope.no_wasmopt,
                 features: scope.features.clone(),
                 abi_features: scope.abi_features.clone(),
+                abi_include: scope.abi_include.clone(),
+                abi_exclude: scope.abi_exclude.clone(),
                 no_default_features: scope.no_default_features,
                 env: scope.env.clone(),
                 out_dir: scope.out_dir.clone(),
@@ -204,6 +224,8 @@ impl From<BuildOpts> for cargo_near_build::BuildOpts {
             no_wasmopt: value.no_wasmopt,
             features: value.features,
             abi_features: value.abi_features,
+            abi_include: value.abi_include,
+            abi_exclude: value.abi_exclude,
             no_default_features: value.no_default_features,
             out_dir: value.out_dir.map(Into::into),
             manifest_path: value.manifest_path.map(Into::into),
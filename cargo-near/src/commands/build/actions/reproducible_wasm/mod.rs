@@ -91,6 +91,81 @@ pub struct BuildOpts {
     #[interactive_clap(skip_interactive_input)]
     #[interactive_clap(verbatim_doc_comment)]
     pub variant: Option<String>,
+    /// Print default `[package.metadata.near.reproducible_build]` and all of its variants, with `extends` resolved, without building
+    ///
+    /// A variant can be applied on top of another one, instead of the default section,
+    /// with `extends = "<other-variant>"`:
+    /// ```toml
+    /// [package.metadata.near.reproducible_build.variant.testnet-debug]
+    /// extends = "testnet"
+    /// passed_env = ["DEBUG_LOG"]
+    /// ```
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub list_variants: bool,
+    /// Build default `[package.metadata.near.reproducible_build]` and all of its variants from a single clone of the repository
+    ///
+    /// Variants are built one after another, an artifact of a variant is named `<contract>.<variant>.wasm`.
+    /// A table of SHA-256 hashes of all artifacts is printed at the end.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub all_variants: bool,
+    /// Build the contract from this remote git repository instead of the local one, e.g. `https://github.com/org/contracts`
+    ///
+    /// The repository is cloned to a temporary build site and `--rev` is checked out,
+    /// `[package.metadata.near.reproducible_build]` of that revision is applied.
+    /// The resulting wasm is copied to `--out-dir`, or to the current directory.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub repo: Option<String>,
+    /// Revision (commit, tag or branch) of `--repo` to build
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub rev: Option<String>,
+    /// Path of the contract's crate in `--repo`, relative to its root; the root itself by default
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub contract_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Build the contract at this revision (commit, tag or branch) of the local repository instead of current HEAD
+    ///
+    /// Uncommitted changes are irrelevant then and aren't checked, the working tree isn't touched.
+    /// The revision is recorded in `source_code_snapshot` of contract's NEP-330 metadata.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub git_ref: Option<String>,
+    /// Allow files, matching the glob, to be modified or untracked, when git checks are performed. This flag can be repeated.
+    ///
+    /// Globs are matched against paths, relative to the root of the repository; a glob without `/`
+    /// matches a file name at any depth, e.g. `--allow-dirty '*.log' --allow-dirty 'notes/**'`.
+    /// Globs are added to `dirty_allowlist` of `[package.metadata.near.reproducible_build]` in Cargo.toml.
+    /// Changed files outside of the contract's crate and its path dependencies are always allowed.
+    #[interactive_clap(verbatim_doc_comment)]
+    #[interactive_clap(long_vec_multiple_opt)]
+    pub allow_dirty: Vec<String>,
+    /// Vendor dependencies with `cargo vendor` on the temporary build site and build without network access
+    ///
+    /// A `.cargo/config.toml` source replacement, pointing to vendored sources, is generated for the contract's crate,
+    /// and the container is run with networking disabled. SHA-256 of vendored sources is printed and recorded in the config.
+    /// Vendored sources are compiled from a different path than registry ones, which is embedded into wasm,
+    /// so its hash differs from the one of a build with network access. Vendoring is recorded in `build_command`
+    /// of NEP-330 build info as `--env CARGO_NEAR_VENDORED_SOURCES=<sha256>`, and `cargo near verify` replays it.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub vendor: bool,
+    /// Container runtime to build with: docker, podman, nerdctl
+    ///
+    /// Defaults to `CARGO_NEAR_CONTAINER_RUNTIME` environment variable, or to docker, if it's unset.
+    /// Rootless podman is supported.
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub container_runtime: Option<crate::types::container_runtime_cli::ContainerRuntimeCli>,
 }
 
 impl From<CliBuildOpts> for BuildOpts {
@@ -101,7 +176,16 @@ impl From<CliBuildOpts> for BuildOpts {
             manifest_path: value.manifest_path,
             color: value.color,
             variant: value.variant,
+            list_variants: value.list_variants,
+            all_variants: value.all_variants,
             profile: value.profile,
+            repo: value.repo,
+            rev: value.rev,
+            contract_path: value.contract_path,
+            git_ref: value.git_ref,
+            allow_dirty: value.allow_dirty,
+            vendor: value.vendor,
+            container_runtime: value.container_runtime,
         }
     }
 }
@@ -121,29 +205,145 @@ mod context {
                 manifest_path: scope.manifest_path.clone(),
                 color: scope.color.clone(),
                 variant: scope.variant.clone(),
+                list_variants: scope.list_variants,
+                all_variants: scope.all_variants,
                 profile: scope.profile.clone(),
+                repo: scope.repo.clone(),
+                rev: scope.rev.clone(),
+                contract_path: scope.contract_path.clone(),
+                git_ref: scope.git_ref.clone(),
+                allow_dirty: scope.allow_dirty.clone(),
+                vendor: scope.vendor,
+                container_runtime: scope.container_runtime.clone(),
             };
-            super::run(opts, previous_context)?;
+            if opts.list_variants {
+                super::list_variants(opts)?;
+            } else if opts.all_variants {
+                super::build_all_variants(opts, previous_context)?;
+            } else {
+                super::run(opts, previous_context)?;
+            }
             Ok(Self)
         }
     }
 }
 
 /// this is more or less equivalent to
-/// impl From<(BuildCommand, BuildContext)> for docker::DockerBuildOpts
+/// impl TryFrom<(BuildCommand, BuildContext)> for docker::DockerBuildOpts
 /// which is not possible due to BuildContext being a non-local type to current (cli) crate
-fn docker_opts_from(value: (BuildOpts, BuildContext)) -> docker::DockerBuildOpts {
-    docker::DockerBuildOpts {
-        no_locked: value.0.no_locked,
-        out_dir: value.0.out_dir.map(Into::into),
-        manifest_path: value.0.manifest_path.map(Into::into),
-        color: value.0.color.map(Into::into),
-        variant: value.0.variant,
-        context: value.1,
+fn docker_opts_from(
+    value: (BuildOpts, BuildContext),
+) -> color_eyre::eyre::Result<docker::DockerBuildOpts> {
+    let (opts, context) = value;
+    let remote = match (opts.repo, opts.rev) {
+        (Some(_), _) if opts.git_ref.is_some() => {
+            return Err(color_eyre::eyre::eyre!(
+                "`--git-ref` can't be combined with `--repo`, use `--rev` instead"
+            ));
+        }
+        (Some(repo), Some(rev)) => {
+            if opts.manifest_path.is_some() {
+                return Err(color_eyre::eyre::eyre!(
+                    "`--manifest-path` can't be combined with `--repo`, use `--contract-path` instead"
+                ));
+            }
+            Some(docker::RemoteSource {
+                repo,
+                rev,
+                contract_path: opts.contract_path.map(Into::into).unwrap_or_default(),
+            })
+        }
+        (Some(_), None) => {
+            return Err(color_eyre::eyre::eyre!(
+                "`--repo` requires `--rev` of the revision to build"
+            ));
+        }
+        (None, rev) if rev.is_some() || opts.contract_path.is_some() => {
+            return Err(color_eyre::eyre::eyre!(
+                "`--rev` and `--contract-path` require `--repo` to build from"
+            ));
+        }
+        (None, _) => None,
+    };
+    Ok(docker::DockerBuildOpts {
+        no_locked: opts.no_locked,
+        out_dir: opts.out_dir.map(Into::into),
+        manifest_path: opts.manifest_path.map(Into::into),
+        color: opts.color.map(Into::into),
+        variant: opts.variant,
+        remote,
+        git_ref: opts.git_ref,
+        allow_dirty: opts.allow_dirty,
+        vendor: opts.vendor,
+        container_runtime: opts.container_runtime.map(Into::into),
+        context,
+    })
+}
+
+fn list_variants(opts: BuildOpts) -> color_eyre::eyre::Result<()> {
+    if opts.repo.is_some() {
+        return Err(color_eyre::eyre::eyre!(
+            "`--list-variants` lists variants of the local crate, it can't be combined with `--repo`"
+        ));
+    }
+    let docker_opts = docker_opts_from((opts, BuildContext::Build))?;
+    cargo_near_build::docker::list_variants(docker_opts)
+}
+
+fn build_all_variants(opts: BuildOpts, context: BuildContext) -> color_eyre::eyre::Result<()> {
+    if opts.variant.is_some() {
+        return Err(color_eyre::eyre::eyre!(
+            "`--all-variants` can't be combined with `--variant`"
+        ));
     }
+    let docker_opts = docker_opts_from((opts, context))?;
+    cargo_near_build::docker::build_all_variants(docker_opts, false)?;
+    Ok(())
 }
 
 pub fn run(opts: BuildOpts, context: BuildContext) -> color_eyre::eyre::Result<BuildArtifact> {
-    let docker_opts = docker_opts_from((opts, context));
+    if opts.list_variants || opts.all_variants {
+        return Err(color_eyre::eyre::eyre!(
+            "`--list-variants` and `--all-variants` can't be combined with a deploy"
+        ));
+    }
+    let docker_opts = docker_opts_from((opts, context))?;
     cargo_near_build::docker::build(docker_opts, false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_variants_is_rejected_with_variant_or_single_artifact_build() {
+        let opts = BuildOpts {
+            all_variants: true,
+            variant: Some("testnet".to_string()),
+            ..Default::default()
+        };
+        let err = build_all_variants(opts, BuildContext::Build).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`--all-variants` can't be combined with `--variant`"),
+            "{err}"
+        );
+
+        for opts in [
+            BuildOpts {
+                all_variants: true,
+                ..Default::default()
+            },
+            BuildOpts {
+                list_variants: true,
+                ..Default::default()
+            },
+        ] {
+            let err = run(opts, BuildContext::Build).err().unwrap();
+            assert!(
+                err.to_string().contains("can't be combined with a deploy"),
+                "{err}"
+            );
+        }
+    }
+}
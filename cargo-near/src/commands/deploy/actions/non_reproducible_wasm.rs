@@ -23,11 +23,15 @@ mod context {
     use crate::commands::build as build_command;
 
     #[derive(Debug, Clone)]
-    pub struct Context(near_cli_rs::commands::contract::deploy::GenericDeployContext);
+    pub struct Context {
+        deploy_context: near_cli_rs::commands::contract::deploy::GenericDeployContext,
+        /// ABI file, written next to the wasm by the build, `None` if ABI generation was skipped
+        pub abi_path: Option<cargo_near_build::camino::Utf8PathBuf>,
+    }
 
     impl From<Context> for near_cli_rs::commands::contract::deploy::GenericDeployContext {
         fn from(item: Context) -> Self {
-            item.0
+            item.deploy_context
         }
     }
 
@@ -47,8 +51,14 @@ mod context {
                     err
                 )
             })?;
-            Ok(Self(
-                near_cli_rs::commands::contract::deploy::GenericDeployContext {
+            let abi_path = match (scope.build_command_opts.no_abi, artifact.path.file_stem()) {
+                (false, Some(stem)) => {
+                    Some(artifact.path.with_file_name(format!("{stem}_abi.json")))
+                }
+                _ => None,
+            };
+            Ok(Self {
+                deploy_context: near_cli_rs::commands::contract::deploy::GenericDeployContext {
                     global_context: previous_context,
                     receiver_account_id: scope.contract_account_id.clone().into(),
                     signer_account_id: scope.contract_account_id.clone().into(),
@@ -56,7 +66,8 @@ mod context {
                         DeployContractAction { code },
                     ),
                 },
-            ))
+                abi_path,
+            })
         }
     }
 }
@@ -76,6 +87,34 @@ mod manual_input {
     }
 }
 
+/// validation of init call arguments, passed on command line, against contract's ABI,
+/// done before the deploy transaction is signed
+mod init_args {
+    use near_cli_rs::commands::contract::call_function::call_function_args_type::FunctionArgsType;
+    use near_cli_rs::commands::contract::deploy::initialize_mode::CliInitializeMode;
+
+    pub fn validate(
+        abi_path: Option<&cargo_near_build::camino::Utf8Path>,
+        initialize: Option<&CliInitializeMode>,
+    ) -> color_eyre::eyre::Result<()> {
+        let (Some(abi_path), Some(CliInitializeMode::WithInitCall(init_call))) =
+            (abi_path, initialize)
+        else {
+            return Ok(());
+        };
+        // only JSON arguments are checked; the ones, entered interactively, are out of reach here
+        let (Some(function_name), Some(FunctionArgsType::JsonArgs), Some(function_args)) = (
+            &init_call.function_name,
+            &init_call.function_args_type,
+            &init_call.function_args,
+        ) else {
+            return Ok(());
+        };
+        let contract_abi = cargo_near_build::abi::validate::read_abi(abi_path)?;
+        cargo_near_build::abi::validate::check(&contract_abi, function_name, function_args)
+    }
+}
+
 /// this module is needed because of #[interactive_clap(skip_default_from_cli)]
 /// on `DeployOpts`
 mod manual_from_cli {
@@ -133,6 +172,13 @@ mod manual_from_cli {
                     }
                 };
 
+            if let Err(err) = super::init_args::validate(
+                output_context.abi_path.as_deref(),
+                clap_variant.initialize.as_ref(),
+            ) {
+                return interactive_clap::ResultFromCli::Err(Some(clap_variant), err);
+            }
+
             match InitializeMode::from_cli(clap_variant.initialize.take(), output_context.into()) {
                 interactive_clap::ResultFromCli::Ok(initialize) => {
                     clap_variant.initialize = Some(initialize);
@@ -0,0 +1,21 @@
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = MetadataContext)]
+pub struct Metadata {
+    /// Path to contract's wasm
+    pub wasm: crate::types::utf8_path_buf::Utf8PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetadataContext;
+
+impl MetadataContext {
+    pub fn from_previous_context(
+        _previous_context: near_cli_rs::GlobalContext,
+        scope: &<Metadata as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let wasm: cargo_near_build::camino::Utf8PathBuf = scope.wasm.clone().into();
+        cargo_near_build::inspect::inspect_metadata(&wasm)?;
+        Ok(Self)
+    }
+}
@@ -0,0 +1,23 @@
+mod actions {
+    mod metadata;
+
+    use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+    #[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
+    #[strum_discriminants(derive(EnumMessage, EnumIter))]
+    #[interactive_clap(context = near_cli_rs::GlobalContext)]
+    pub enum Actions {
+        #[strum_discriminants(strum(
+            message = "metadata  - Print NEP-330 contract source metadata, embedded in the wasm"
+        ))]
+        /// Print NEP-330 contract source metadata, returned by `contract_source_metadata` method of the wasm, without a NEAR node
+        Metadata(self::metadata::Metadata),
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+pub struct Command {
+    #[interactive_clap(subcommand)]
+    actions: actions::Actions,
+}
@@ -5,8 +5,12 @@ pub mod build;
 pub mod check;
 pub mod create_dev_account;
 pub mod deploy;
+pub mod inspect;
 pub mod new;
+pub mod reproducible_build;
+pub mod run_view;
 pub mod self_update;
+pub mod verify;
 
 #[derive(Debug, EnumDiscriminants, Clone, interactive_clap::InteractiveClap)]
 #[interactive_clap(context = near_cli_rs::GlobalContext)]
@@ -35,6 +39,26 @@ pub enum NearCommand {
     ))]
     /// Type-check a NEAR contract (runs `cargo check`, or `cargo clippy` with `--clippy`) under the same environment as `cargo near build`, without producing a wasm artifact
     Check(self::check::Command),
+    #[strum_discriminants(strum(
+        message = "inspect             -  Inspect contract's wasm offline"
+    ))]
+    /// Inspect contract's wasm offline
+    Inspect(self::inspect::Command),
+    #[strum_discriminants(strum(
+        message = "run-view            -  Call a view method of contract's wasm locally, against an optional state dump"
+    ))]
+    /// Call a view method of contract's wasm in a local interpreter, against an optional state dump
+    RunView(self::run_view::Command),
+    #[strum_discriminants(strum(
+        message = "verify              -  Reproduce a contract's build from its NEP-330 source metadata and compare the wasm hash"
+    ))]
+    /// Reproduce a contract's build from its NEP-330 source metadata and compare the wasm hash
+    Verify(self::verify::Command),
+    #[strum_discriminants(strum(
+        message = "reproducible-build  -  Manage configuration of reproducible builds in contract's Cargo.toml"
+    ))]
+    /// Manage `[package.metadata.near.reproducible_build]` configuration in contract's Cargo.toml
+    ReproducibleBuild(self::reproducible_build::Command),
     #[strum_discriminants(strum(
         message = "create-dev-account  -  Create a development account using a faucet service sponsor and receive some NEAR tokens (testnet only).
 │                            To create an account on mainnet, use NEAR CLI [https://near.cli.rs]"
@@ -0,0 +1,169 @@
+use cargo_near_build::docker::upgrade_image::{SOURCE_SCAN_IMAGE, UpgradeImageOpts};
+use color_eyre::eyre::WrapErr;
+
+const DOCKER_HUB: &str = "https://hub.docker.com";
+/// upper bound of requested pages of tags, 100 tags each
+const MAX_PAGES: usize = 50;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = UpgradeImageContext)]
+pub struct UpgradeImage {
+    /// Only consider images with this rust toolchain version, e.g. `1.85` or `1.85.0`
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub rust: Option<String>,
+    /// Upgrade `[package.metadata.near.reproducible_build.variant.<VARIANT>]` instead of the default section
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub variant: Option<String>,
+    /// Path to the `Cargo.toml` of the contract; `Cargo.toml` in current directory by default
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub manifest_path: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Base url of a registry with Docker Hub API, which tags of the image are queried from
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub registry_url: Option<String>,
+    /// Whether to color output to stdout and stderr by printing ANSI escape sequences: auto, always, never
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    pub color: Option<crate::types::color_preference_cli::ColorPreferenceCli>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpgradeImageContext;
+
+impl UpgradeImageContext {
+    pub fn from_previous_context(
+        _previous_context: near_cli_rs::GlobalContext,
+        scope: &<UpgradeImage as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let registry_url = scope.registry_url.as_deref().unwrap_or(DOCKER_HUB);
+        let tags = fetch_tags(registry_url)?;
+        let tag =
+            cargo_near_build::docker::upgrade_image::select_latest(tags, scope.rust.as_deref())?;
+        let opts = UpgradeImageOpts {
+            manifest_path: scope.manifest_path.clone().map(Into::into),
+            variant: scope.variant.clone(),
+            tag,
+            color: scope.color.clone().map(Into::into),
+        };
+        cargo_near_build::docker::upgrade_image::upgrade_image(opts)?;
+        Ok(Self)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TagsPage {
+    next: Option<String>,
+    results: Vec<TagEntry>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TagEntry {
+    name: String,
+    /// digest of the (multi-platform) image, absent for tags without pushed images
+    digest: Option<String>,
+}
+
+/// pairs of tag and digest of [`SOURCE_SCAN_IMAGE`] from all pages of `tags` endpoint of Docker Hub API
+fn fetch_tags(registry_url: &str) -> color_eyre::eyre::Result<Vec<(String, String)>> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let client = reqwest::Client::new();
+    let mut next = Some(format!(
+        "{}/v2/repositories/{}/tags?page_size=100",
+        registry_url.trim_end_matches('/'),
+        SOURCE_SCAN_IMAGE
+    ));
+    let mut tags = vec![];
+    for _ in 0..MAX_PAGES {
+        let Some(url) = next.take() else {
+            break;
+        };
+        let body = runtime
+            .block_on(async {
+                client
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await
+            })
+            .wrap_err_with(|| {
+                format!("unable to query tags of `{SOURCE_SCAN_IMAGE}` at `{url}`")
+            })?;
+        let page: TagsPage = serde_json::from_slice(&body)
+            .wrap_err_with(|| format!("unexpected response of `{url}`"))?;
+        tags.extend(
+            page.results
+                .into_iter()
+                .filter_map(|entry| Some((entry.name, entry.digest?))),
+        );
+        next = page.next;
+    }
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+
+    /// local stand-in of a registry, which serves one page per connection, in order;
+    /// `pages` are built with the address of the server, so that they can link to the next ones
+    fn serve(pages: impl FnOnce(&str) -> Vec<serde_json::Value>) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let pages = pages(&address);
+        std::thread::spawn(move || {
+            for page in pages {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let page = page.to_string();
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    page.len(),
+                    page
+                )
+                .unwrap();
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn fetch_tags_follows_pages() {
+        let address = serve(|address| {
+            vec![
+                serde_json::json!({
+                    "next": format!("{address}/v2/repositories/sourcescan/cargo-near/tags?page=2"),
+                    "results": [{ "name": "0.13.4-rust-1.85.0", "digest": "sha256:1" }],
+                }),
+                serde_json::json!({
+                    "next": null,
+                    "results": [
+                        { "name": "0.14.1-rust-1.86.0", "digest": "sha256:3" },
+                        { "name": "0.14.0-rust-1.86.0" },
+                    ],
+                }),
+            ]
+        });
+        let tags = super::fetch_tags(&address).unwrap();
+        assert_eq!(
+            tags,
+            vec![
+                ("0.13.4-rust-1.85.0".to_string(), "sha256:1".to_string()),
+                ("0.14.1-rust-1.86.0".to_string(), "sha256:3".to_string()),
+            ]
+        );
+        let latest = cargo_near_build::docker::upgrade_image::select_latest(tags, None).unwrap();
+        assert_eq!(latest.tag, "0.14.1-rust-1.86.0");
+    }
+}
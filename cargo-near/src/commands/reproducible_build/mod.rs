@@ -0,0 +1,23 @@
+mod actions {
+    mod upgrade_image;
+
+    use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+    #[derive(Debug, Clone, EnumDiscriminants, interactive_clap::InteractiveClap)]
+    #[strum_discriminants(derive(EnumMessage, EnumIter))]
+    #[interactive_clap(context = near_cli_rs::GlobalContext)]
+    pub enum Actions {
+        #[strum_discriminants(strum(
+            message = "upgrade-image  - Upgrade docker image of reproducible build in Cargo.toml to the latest one"
+        ))]
+        /// Upgrade `image` and `image_digest` of `[package.metadata.near.reproducible_build]` in Cargo.toml to the latest `sourcescan/cargo-near` tag
+        UpgradeImage(self::upgrade_image::UpgradeImage),
+    }
+}
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(context = near_cli_rs::GlobalContext)]
+pub struct Command {
+    #[interactive_clap(subcommand)]
+    actions: actions::Actions,
+}
@@ -0,0 +1,48 @@
+use cargo_near_build::run_view::RunViewOpts;
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = RunViewCommandlContext)]
+pub struct Command {
+    /// Path to contract's wasm
+    pub wasm: crate::types::utf8_path_buf::Utf8PathBuf,
+    /// Name of the view method to call
+    pub method: String,
+    /// Arguments of the method, e.g. '{"account_id": "alice.near"}'
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub args: Option<String>,
+    /// Path to a JSON dump of contract's state, e.g. result of `view_state` RPC query
+    ///
+    /// Expected format is `{"values": [{"key": "<base64>", "value": "<base64>"}, ...]}`,
+    /// state is empty, if this argument isn't specified.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub state: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Whether to color output to stdout and stderr by printing ANSI escape sequences: auto, always, never
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    pub color: Option<crate::types::color_preference_cli::ColorPreferenceCli>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RunViewCommandlContext;
+
+impl RunViewCommandlContext {
+    pub fn from_previous_context(
+        _previous_context: near_cli_rs::GlobalContext,
+        scope: &<Command as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let opts = RunViewOpts {
+            wasm: scope.wasm.clone().into(),
+            method: scope.method.clone(),
+            args: scope.args.clone(),
+            state: scope.state.clone().map(Into::into),
+            color: scope.color.clone().map(Into::into),
+        };
+        cargo_near_build::run_view::run_view(opts)?;
+        Ok(Self)
+    }
+}
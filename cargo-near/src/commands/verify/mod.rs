@@ -0,0 +1,71 @@
+use cargo_near_build::docker::verify::{ExpectedHash, VerifyOpts};
+
+#[derive(Debug, Clone, interactive_clap::InteractiveClap)]
+#[interactive_clap(input_context = near_cli_rs::GlobalContext)]
+#[interactive_clap(output_context = VerifyCommandlContext)]
+pub struct Command {
+    /// Path to a JSON file with NEP-330 `ContractSourceMetadata` of the contract
+    ///
+    /// Its `build_info` (docker image with digest, build command, contract path and
+    /// source code snapshot) describes the reproducible build to be performed.
+    /// If not specified, it's extracted from `--wasm` with `cargo near inspect metadata`.
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub metadata: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// Path to a wasm file, which SHA-256 the reproduced wasm is compared against
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub wasm: Option<crate::types::utf8_path_buf::Utf8PathBuf>,
+    /// SHA-256 of the wasm, the reproduced wasm is compared against, in hex or in base58 (as NEAR code hash)
+    #[interactive_clap(long)]
+    #[interactive_clap(skip_interactive_input)]
+    pub hash: Option<String>,
+    /// Container runtime to build with: docker, podman, nerdctl
+    ///
+    /// Defaults to `CARGO_NEAR_CONTAINER_RUNTIME` environment variable, or to docker, if it's unset.
+    /// Rootless podman is supported.
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    #[interactive_clap(verbatim_doc_comment)]
+    pub container_runtime: Option<crate::types::container_runtime_cli::ContainerRuntimeCli>,
+    /// Whether to color output to stdout and stderr by printing ANSI escape sequences: auto, always, never
+    #[interactive_clap(long)]
+    #[interactive_clap(value_enum)]
+    #[interactive_clap(skip_interactive_input)]
+    pub color: Option<crate::types::color_preference_cli::ColorPreferenceCli>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyCommandlContext;
+
+impl VerifyCommandlContext {
+    pub fn from_previous_context(
+        _previous_context: near_cli_rs::GlobalContext,
+        scope: &<Command as interactive_clap::ToInteractiveClapContextScope>::InteractiveClapContextScope,
+    ) -> color_eyre::eyre::Result<Self> {
+        let expected = match (scope.wasm.clone(), scope.hash.clone()) {
+            (Some(wasm), None) => ExpectedHash::Wasm(wasm.into()),
+            (None, Some(hash)) => ExpectedHash::Checksum(hash),
+            (Some(_), Some(_)) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "`--wasm` and `--hash` can't be combined"
+                ));
+            }
+            (None, None) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "either `--wasm` or `--hash` is required to compare reproduced wasm against"
+                ));
+            }
+        };
+        let opts = VerifyOpts {
+            metadata: scope.metadata.clone().map(Into::into),
+            expected,
+            container_runtime: scope.container_runtime.clone().map(Into::into),
+            color: scope.color.clone().map(Into::into),
+        };
+        cargo_near_build::docker::verify::verify(opts)?;
+        Ok(Self)
+    }
+}
@@ -0,0 +1,33 @@
+use strum::{EnumDiscriminants, EnumIter, EnumMessage};
+
+#[derive(Debug, EnumDiscriminants, Clone, clap::ValueEnum)]
+#[strum_discriminants(derive(EnumMessage, EnumIter))]
+pub enum ContainerRuntimeCli {
+    Docker,
+    Podman,
+    Nerdctl,
+}
+
+impl interactive_clap::ToCli for ContainerRuntimeCli {
+    type CliVariant = ContainerRuntimeCli;
+}
+
+impl From<ContainerRuntimeCli> for cargo_near_build::docker::ContainerRuntime {
+    fn from(value: ContainerRuntimeCli) -> Self {
+        match value {
+            ContainerRuntimeCli::Docker => Self::Docker,
+            ContainerRuntimeCli::Podman => Self::Podman,
+            ContainerRuntimeCli::Nerdctl => Self::Nerdctl,
+        }
+    }
+}
+
+impl std::fmt::Display for ContainerRuntimeCli {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Docker => write!(f, "docker"),
+            Self::Podman => write!(f, "podman"),
+            Self::Nerdctl => write!(f, "nerdctl"),
+        }
+    }
+}
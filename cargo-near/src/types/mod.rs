@@ -1,3 +1,4 @@
 pub mod color_preference_cli;
+pub mod container_runtime_cli;
 pub mod my_formatter;
 pub mod utf8_path_buf;
@@ -115,6 +115,8 @@ pub fn invoke_cargo_near(
                 out_dir: cmd.out_dir.map(Into::into),
                 manifest_path: Some(cargo_path),
                 features: cmd.features,
+                abi_include: cmd.abi_include,
+                abi_exclude: cmd.abi_exclude,
                 color: cmd.color.map(Into::into),
             };
             tracing::debug!("AbiOpts: {:#?}", args);
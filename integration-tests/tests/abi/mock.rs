@@ -0,0 +1,74 @@
+use std::process::{Command, Stdio};
+
+use cargo_near_integration_tests::{
+    common_root_for_test_projects_build, generate_abi_with, setup_tracing,
+};
+use function_name::named;
+
+/// mock crate, generated from ABI of a contract with recursive types and callbacks, passes `cargo check`
+#[test]
+#[named]
+fn test_abi_mock_crate_checks() -> testresult::TestResult {
+    setup_tracing();
+    let abi_root = generate_abi_with! {
+        Code:
+        use near_sdk::near;
+
+        #[near(serializers = [json])]
+        pub struct Node {
+            pub value: String,
+            pub next: Option<Box<Node>>,
+            pub children: Vec<Node>,
+        }
+
+        #[near(contract_state)]
+        #[derive(Default)]
+        pub struct Contract {}
+
+        #[near]
+        impl Contract {
+            pub fn root(&self) -> Option<Node> {
+                None
+            }
+
+            #[private]
+            pub fn on_fetched(
+                &mut self,
+                key: String,
+                #[callback_unwrap] node: Node,
+                #[callback_result] count: Result<u32, near_sdk::PromiseError>,
+            ) {
+            }
+        }
+    };
+    let on_fetched = abi_root
+        .body
+        .functions
+        .iter()
+        .find(|function| function.name == "on_fetched")
+        .expect("`on_fetched` is in ABI");
+    assert_eq!(on_fetched.callbacks.len(), 2);
+
+    let abi_path = common_root_for_test_projects_build()
+        .join(function_name!())
+        .join("abi.json");
+    std::fs::write(&abi_path, serde_json::to_vec(&abi_root)?)?;
+    // outside of this repository's workspace, which the mock crate doesn't belong to
+    let tmp_dir = tempfile::tempdir()?;
+    let mock_dir = camino::Utf8PathBuf::try_from(tmp_dir.path().join("mock_contract"))?;
+    cargo_near_build::abi::mock::mock(&abi_path, &mock_dir, None)?;
+
+    let output = Command::new("cargo")
+        .arg("check")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .current_dir(&mock_dir)
+        .output()?;
+    assert!(
+        output.status.success(),
+        "`cargo check` of the mock crate failed:\n{}\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    Ok(())
+}
@@ -2,6 +2,7 @@ mod borsh_schema;
 mod callbacks;
 mod e2e;
 mod json_schema;
+mod mock;
 mod modifiers;
 mod negative;
 mod opts;
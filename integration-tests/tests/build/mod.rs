@@ -3,6 +3,7 @@ use cargo_near_integration_tests::build_fn;
 use function_name::named;
 
 mod dynamic_max_rustc;
+mod nep330;
 mod opts;
 mod toolchain;
 
@@ -0,0 +1,49 @@
+use cargo_near_integration_tests::{build_with, setup_tracing};
+use function_name::named;
+
+/// NEP-330 metadata, embedded into the contract by `near-sdk`, matches the one, computed by `cargo near`:
+/// `version` is `package.version` of Cargo.toml, standards are only the declared ones and `nep330`
+#[test]
+#[named]
+fn test_build_nep330_contract_source_metadata() -> testresult::TestResult {
+    setup_tracing();
+    let build_result = build_with! {
+        Code:
+        use near_sdk::near;
+
+        #[near(contract_state, contract_metadata(
+            standard(standard = "nep141", version = "1.0.0"),
+        ))]
+        #[derive(Default)]
+        pub struct Contract {}
+
+        #[near]
+        impl Contract {
+            pub fn add(&self, a: u32, b: u32) -> u32 {
+                a + b
+            }
+        }
+    };
+    let tmp_dir = tempfile::tempdir()?;
+    let wasm_path = camino::Utf8PathBuf::try_from(tmp_dir.path().join("contract.wasm"))?;
+    std::fs::write(&wasm_path, &build_result.wasm)?;
+
+    let metadata = cargo_near_build::inspect::contract_source_metadata(&wasm_path)?;
+    assert_eq!(metadata.version.as_deref(), Some("0.1.0"));
+    let mut standards = metadata
+        .standards
+        .iter()
+        .map(|standard| standard.standard.as_str())
+        .collect::<Vec<_>>();
+    standards.sort();
+    assert_eq!(standards, ["nep141", "nep330"]);
+    assert!(
+        metadata
+            .standards
+            .iter()
+            .any(|standard| standard.standard == "nep141" && standard.version == "1.0.0")
+    );
+    // not a reproducible build
+    assert!(metadata.build_info.is_none());
+    Ok(())
+}